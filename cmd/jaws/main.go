@@ -3,18 +3,29 @@ package main
 import (
 	"errors"
 	"fmt"
-	"log"
 	"os"
 	"path/filepath"
 	"strings"
+	"time"
 
+	"github.com/fatih/color"
 	"github.com/jacbart/jaws/pkg/secretsmanager"
 	"github.com/jacbart/jaws/utils/helpers"
 	"github.com/spf13/cobra"
 )
 
 func main() {
-	cobra.CheckErr(rootCmd.Execute())
+	err := rootCmd.Execute()
+	helpers.EndCIGroup()
+	if code := secretsmanager.ErrorCode(err); code != "" {
+		fmt.Fprintf(os.Stderr, "Error %s: %s\nrun `jaws explain %s` for the cause and fix\n", code, helpers.Redact(err.Error()), code)
+	} else if err != nil {
+		fmt.Fprintln(os.Stderr, "Error:", helpers.Redact(err.Error()))
+	}
+	if err != nil && helpers.CIEnabled(ciMode) {
+		helpers.CIError(helpers.Redact(err.Error()))
+	}
+	os.Exit(secretsmanager.ExitCode(err))
 }
 
 func commands() {
@@ -23,8 +34,15 @@ func commands() {
 	// add path command and sub commands
 	rootCmd.AddCommand(pathCmd)
 	pathCmd.AddCommand(pathCommandCmd)
+	// add checkpoint command and sub commands
+	rootCmd.AddCommand(checkpointCmd)
+	checkpointCmd.AddCommand(checkpointCreateCmd)
+	checkpointCmd.AddCommand(checkpointListCmd)
+	checkpointCmd.AddCommand(checkpointRestoreCmd)
 	// add clean command
 	rootCmd.AddCommand(cleanCmd)
+	// add compose command
+	rootCmd.AddCommand(composeCmd)
 	// add create command
 	rootCmd.AddCommand(createCmd)
 	// add delete command and sub cancel command
@@ -34,44 +52,142 @@ func commands() {
 	rootCmd.AddCommand(diffCmd)
 	// add status command
 	rootCmd.AddCommand(statusCmd)
+	// add doctor command
+	rootCmd.AddCommand(doctorCmd)
+	// add docker credential helper command
+	rootCmd.AddCommand(dockerCredentialHelperCmd)
 	// add get command
 	rootCmd.AddCommand(getCmd)
+	// add explain command
+	rootCmd.AddCommand(explainCmd)
+	// add history command
+	rootCmd.AddCommand(historyCmd)
+	// add k8s-sync command
+	rootCmd.AddCommand(k8sSyncCmd)
 	// add list command
 	rootCmd.AddCommand(listCmd)
+	// add log command
+	rootCmd.AddCommand(logCmd)
+	// add policy command and sub commands
+	rootCmd.AddCommand(policyCmd)
+	policyCmd.AddCommand(policyShowCmd)
+	policyCmd.AddCommand(policySetCmd)
+	// add replicate command
+	rootCmd.AddCommand(replicateCmd)
+	// add resolve command
+	rootCmd.AddCommand(resolveCmd)
 	// add rollback command
 	rootCmd.AddCommand(rollbackCmd)
 	// add set command
 	rootCmd.AddCommand(setCmd)
+	// add vcs command and sub commands
+	rootCmd.AddCommand(vcsCmd)
+	vcsCmd.AddCommand(vcsPushCmd)
+	vcsCmd.AddCommand(vcsGCCmd)
+	vcsCmd.AddCommand(vcsExportGitCmd)
+	// add watch command
+	rootCmd.AddCommand(watchCmd)
+	// add agent command and sub commands
+	rootCmd.AddCommand(agentCmd)
+	agentCmd.AddCommand(agentInstallCmd)
+	agentCmd.AddCommand(agentUninstallCmd)
+	agentCmd.AddCommand(agentStatusCmd)
 	// add config command
 	rootCmd.AddCommand(configCmd)
 	configCmd.AddCommand(configShowCmd)
 	configCmd.AddCommand(configCreateCmd)
 
+	// check the policy before any command that changes the secrets manager
+	for _, c := range []*cobra.Command{setCmd, deleteCmd, deleteCancelCmd, rollbackCmd, replicateCmd, policySetCmd} {
+		preRun := c.PreRunE
+		c.PreRunE = func(cmd *cobra.Command, args []string) error {
+			if err := helpers.CheckProfile(secretManager.ProfileName(), confirmProd); err != nil {
+				return err
+			}
+			if preRun != nil {
+				return preRun(cmd, args)
+			}
+			return nil
+		}
+	}
 }
 
 func flags() {
 	// global persistent flags
 	rootCmd.PersistentFlags().StringVar(&secretsPath, "path", "secrets", "sets download path for secrets, overrides config")
 	rootCmd.PersistentFlags().StringVarP(&cfgFile, "config", "c", "", "set config file")
+	rootCmd.PersistentFlags().StringVar(&profileName, "profile", "", "manager profile to use, overrides default_profile in the config")
+	rootCmd.PersistentFlags().BoolVar(&noVCS, "no-vcs", false, "do not keep any git history in the secrets folder, overrides config")
+	rootCmd.PersistentFlags().BoolVarP(&quiet, "quiet", "q", false, "print nothing to stdout, only errors and the exit code report the result")
+	rootCmd.PersistentFlags().BoolVar(&nonInteractive, "non-interactive", false, "never prompt, fail with an error instead, also applies when stdin is not a terminal")
+	rootCmd.PersistentFlags().BoolVar(&ciMode, "ci", false, "for pipelines, env var only auth, no prompts, short retries and grouped logs, same as JAWS_CI=1")
+	rootCmd.PersistentFlags().BoolVar(&confirmProd, "confirm-prod", false, "allow changes to profiles protected by the policy block of the config")
+	rootCmd.PersistentFlags().StringVarP(&outputFormat, "output", "o", helpers.OutputTable, "output format, table, json, yaml or csv")
+	rootCmd.RegisterFlagCompletionFunc("output", cobra.FixedCompletions(
+		[]string{helpers.OutputTable, helpers.OutputJSON, helpers.OutputYAML, helpers.OutputCSV},
+		cobra.ShellCompDirectiveNoFileComp,
+	))
 	// version command flags
 	versionCmd.Flags().BoolVarP(&rawVersion, "raw", "r", false, "return version only")
+	// compose command flags
+	composeCmd.Flags().StringSliceVarP(&composeSecrets, "secret", "s", nil, "secrets passed to docker compose as env vars, json objects give one var per key")
+	composeCmd.MarkFlagRequired("secret")
 	// create command flags
 	createCmd.Flags().BoolVarP(&useEditor, "editor", "e", false, "open any selected secrets in an editor")
 	// delete command flags
 	deleteCmd.Flags().Int64Var(&scheduleInDays, "days", 30, "set time till deletion in days, minimum 7")
+	// diff command flags
+	diffCmd.Flags().BoolVarP(&maskValues, "mask", "m", false, "mask secret values in the diff, changed keys are still shown")
+	// doctor command flags
+	doctorCmd.Flags().BoolVar(&fixPerms, "fix-perms", false, "restrict the secrets folder and its files to the current user")
+	// docker credential helper command flags
+	dockerCredentialHelperCmd.Flags().StringVar(&dockerPrefix, "prefix", secretsmanager.DefaultDockerPrefix, "folder the registry credentials are stored under")
 	// get command flags
 	getCmd.Flags().BoolVarP(&cleanPrintValue, "print", "p", false, "print secret string to terminal instead of downloading to a file")
 	getCmd.Flags().BoolVarP(&formatPrintValue, "fmt-print", "f", false, "print formatted secret string to terminal instead of downloading to a file")
 	getCmd.Flags().BoolVarP(&useEditor, "editor", "e", false, "open any selected secrets in an editor")
+	getCmd.Flags().StringToStringVarP(&tagFilter, "tag", "t", nil, "only offer secrets with these tags in the fuzzy finder, key=value")
+	getCmd.Flags().StringVar(&prefixFilter, "prefix", "", "only offer secrets whose name starts with prefix in the fuzzy finder")
+	// k8s-sync command flags
+	k8sSyncCmd.Flags().StringVarP(&k8sNamespace, "namespace", "n", "", "namespace set on the rendered secrets")
+	k8sSyncCmd.Flags().StringVar(&k8sMapFile, "map", "", "yaml file mapping kubernetes secrets to jaws secrets")
+	k8sSyncCmd.MarkFlagRequired("map")
+	k8sSyncCmd.Flags().BoolVar(&k8sApply, "apply", false, "apply the secrets with kubectl instead of printing them")
+	k8sSyncCmd.Flags().DurationVar(&k8sWatch, "watch", 0, "render again every interval and apply or print the secrets when they change")
+	// list command flags
+	listCmd.Flags().StringToStringVarP(&tagFilter, "tag", "t", nil, "only list secrets with these tags, key=value")
+	listCmd.Flags().StringVar(&prefixFilter, "prefix", "", "only list secrets whose name starts with prefix")
+	listCmd.Flags().BoolVarP(&longList, "long", "l", false, "list secrets with their tags and rotation schedule")
+	// history command flags
+	historyCmd.Flags().BoolVar(&historyVCS, "vcs", false, "show the local history of the secret from the secrets folder (default)")
+	historyCmd.Flags().BoolVar(&historyRemote, "remote", false, "show the versions stored in the secrets manager and their staging labels")
+	// vcs gc command flags
+	vcsGCCmd.Flags().IntVar(&gcKeepDays, "days", 90, "keep the commits from the last number of days, older commits are squashed")
+	// watch command flags
+	watchCmd.Flags().DurationVar(&watchInterval, "interval", 2*time.Second, "how often to check the secrets folder for changes")
+	// agent install command flags
+	agentInstallCmd.Flags().DurationVar(&watchInterval, "interval", 2*time.Second, "how often the agent checks the secrets folder for changes")
+	// log command flags
+	logCmd.Flags().StringVarP(&logDiffRev, "diff", "d", "", "show the content diff of a commit in the secrets folder")
+	logCmd.Flags().BoolVarP(&maskValues, "mask", "m", false, "mask secret values in the diff, changed keys are still shown")
+	// policy set command flags
+	policySetCmd.Flags().StringVarP(&policyFile, "file", "f", "", "json file with the resource policy")
+	policySetCmd.MarkFlagRequired("file")
+	// replicate command flags
+	replicateCmd.Flags().StringSliceVar(&replicaRegions, "regions", nil, "regions to replicate to, defaults to replica_regions in the config")
 	// set command flags
 	setCmd.Flags().BoolVar(&createPrompt, "no-prompt", false, "add this flag to skip the confirmation prompt of new secrets")
 	setCmd.Flags().BoolVarP(&cleanLocalSecrets, "keep-secrets", "k", false, "set to keep secrets after pushing/setting them")
+	setCmd.Flags().BoolVarP(&assumeYes, "yes", "y", false, "push without showing the summary of changes and asking for confirmation")
+	setCmd.Flags().StringVar(&kmsKeyID, "kms-key", "", "kms key id, arn or alias used to encrypt newly created secrets, overrides config")
 }
 
 var (
 	secretManager     secretsmanager.Manager
+	configErr         error
 	jawsConf          secretsmanager.JawsConfig
 	cfgFile           string
+	profileName       string
 	secretsPath       string
 	scheduleInDays    int64
 	useEditor         bool
@@ -80,6 +196,34 @@ var (
 	createPrompt      bool
 	cleanLocalSecrets bool
 	rawVersion        bool
+	historyVCS        bool
+	historyRemote     bool
+	noVCS             bool
+	vcsRemote         string
+	validators        helpers.Validators
+	watchInterval     time.Duration
+	tagFilter         map[string]string
+	prefixFilter      string
+	longList          bool
+	kmsKeyID          string
+	replicaRegions    []string
+	policyFile        string
+	gcKeepDays        int
+	logDiffRev        string
+	maskValues        bool
+	outputFormat      string
+	nonInteractive    bool
+	ciMode            bool
+	confirmProd       bool
+	quiet             bool
+	assumeYes         bool
+	fixPerms          bool
+	dockerPrefix      string
+	k8sNamespace      string
+	k8sMapFile        string
+	k8sApply          bool
+	k8sWatch          time.Duration
+	composeSecrets    []string
 	Version           string
 	Date              string
 
@@ -90,7 +234,9 @@ var (
 		Long: `jaws is a cli tool to interact with secrets managers.
 A recommened secrets format is ENV/APP/DEPLOYMENT/SecretType. When downloading
 secrets they will create a path using the name of the secret, it requires the same format when uploading secrets.`,
-		Example: "jaws get --print",
+		Example:           "jaws get --print",
+		SilenceErrors:     true,
+		PersistentPreRunE: requireConfig,
 	}
 
 	// versionCmd represents the set command
@@ -129,6 +275,45 @@ secrets they will create a path using the name of the secret, it requires the sa
 		},
 	}
 
+	// checkpointCmd represents the checkpoint command
+	checkpointCmd = &cobra.Command{
+		Use:               "checkpoint",
+		Short:             "name points in the secrets folder history and restore them later",
+		PersistentPreRunE: requireVCS,
+	}
+
+	// checkpointCreateCmd represents the checkpoint create command
+	checkpointCreateCmd = &cobra.Command{
+		Use:     "create",
+		Short:   "tag the current state of the secrets folder with a name",
+		Example: "jaws checkpoint create before-rotation-2024Q4",
+		Args:    cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			return helpers.CreateCheckpoint(secretsPath, args[0])
+		},
+	}
+
+	// checkpointListCmd represents the checkpoint list command
+	checkpointListCmd = &cobra.Command{
+		Use:     "list",
+		Short:   "list checkpoints in the secrets folder",
+		Aliases: []string{"ls"},
+		RunE: func(cmd *cobra.Command, args []string) error {
+			return helpers.ListCheckpoints(secretsPath)
+		},
+	}
+
+	// checkpointRestoreCmd represents the checkpoint restore command
+	checkpointRestoreCmd = &cobra.Command{
+		Use:     "restore",
+		Short:   "restore the secrets folder to a checkpoint, the restore is recorded as a new commit",
+		Example: "jaws checkpoint restore before-rotation-2024Q4",
+		Args:    cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			return helpers.RestoreCheckpoint(secretsPath, args[0], secretManager.ProfileName())
+		},
+	}
+
 	// cleanCmd represents the set command
 	cleanCmd = &cobra.Command{
 		Use:     "clean",
@@ -139,12 +324,44 @@ secrets they will create a path using the name of the secret, it requires the sa
 		},
 	}
 
+	// composeCmd represents the compose command
+	composeCmd = &cobra.Command{
+		Use:   "compose",
+		Short: "run docker compose with secrets as env vars from an env file that is shredded on exit",
+		Long: `run docker compose with secrets as env vars. The secrets are written to a private env file, in
+/dev/shm when available, passed with --env-file and shredded when compose exits, so no plaintext
+env file is kept next to the compose project.`,
+		Example: "jaws compose -s testing/app/default/env -s testing/app/default/db#password -- up -d",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			secrets, err := secretManager.Get(composeSecrets)
+			if err != nil {
+				return err
+			}
+			if len(secrets) != len(composeSecrets) {
+				return fmt.Errorf("resolving the compose secrets: %w", secretsmanager.ErrNotFound)
+			}
+			var env []string
+			for _, s := range secrets {
+				lines, err := secretsmanager.EnvLines(s)
+				if err != nil {
+					return err
+				}
+				env = append(env, lines...)
+			}
+			return helpers.RunCompose(env, args)
+		},
+	}
+
 	// createCmd represents the set command
 	createCmd = &cobra.Command{
 		Use:     "create",
 		Short:   "creates folder path and empty file to edit",
 		Aliases: []string{"c"},
 		RunE: func(cmd *cobra.Command, args []string) error {
+			args, err := helpers.StdinArgs(args)
+			if err != nil {
+				return err
+			}
 			return secretManager.Create(args, secretsPath, useEditor)
 		},
 	}
@@ -161,10 +378,15 @@ secrets they will create a path using the name of the secret, it requires the sa
 
 	// deleteCancelCmd represents the delete sub command cancel
 	deleteCancelCmd = &cobra.Command{
-		Use:     "cancel",
-		Short:   "cancel a scheduled secret deletion",
-		Example: "jaws delete cancel testing/app/default/secret",
+		Use:               "cancel",
+		Short:             "cancel a scheduled secret deletion",
+		Example:           "jaws delete cancel testing/app/default/secret",
+		ValidArgsFunction: completeSecretIDs,
 		RunE: func(cmd *cobra.Command, args []string) error {
+			args, err := helpers.StdinArgs(args)
+			if err != nil {
+				return err
+			}
 			return secretManager.DeleteCancel(args)
 		},
 	}
@@ -172,9 +394,9 @@ secrets they will create a path using the name of the secret, it requires the sa
 	// diffCmd represents the set command
 	diffCmd = &cobra.Command{
 		Use:   "diff",
-		Short: "uses git to compare original secret with the changed secret, you can run git diff in the secrets location to get the same results",
+		Short: "compare the changed secrets with their last commit in the secrets folder, use --mask to hide values",
 		RunE: func(cmd *cobra.Command, args []string) error {
-			return helpers.GitDiff(secretsPath)
+			return helpers.GitDiff(secretsPath, maskValues)
 		},
 	}
 
@@ -183,10 +405,71 @@ secrets they will create a path using the name of the secret, it requires the sa
 		Use:   "status",
 		Short: "uses git status to compare original secret with the changed secret",
 		RunE: func(cmd *cobra.Command, args []string) error {
+			if outputFormat != helpers.OutputTable {
+				entries, err := helpers.GitStatusEntries(secretsPath)
+				if err != nil {
+					return err
+				}
+				return helpers.PrintOutput(outputFormat, entries, helpers.StatusTable(entries))
+			}
 			return helpers.GitStatus(secretsPath)
 		},
 	}
 
+	// doctorCmd represents the doctor command
+	doctorCmd = &cobra.Command{
+		Use:     "doctor",
+		Short:   "check the secrets folder for permissions readable by other users and for cloud synced locations",
+		Example: "jaws doctor --fix-perms",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			if dir := helpers.CloudSynced(secretsPath); dir != "" {
+				fmt.Printf("%s %s is inside %s, secrets are uploaded by its sync client\n", color.YellowString("warning:"), secretsPath, dir)
+			}
+			if fixPerms {
+				fixed, err := helpers.FixPermissions(secretsPath)
+				if err != nil {
+					return err
+				}
+				fmt.Printf("%d permission(s) %s\n", fixed, color.GreenString("fixed"))
+				return nil
+			}
+			problems, err := helpers.PermissionProblems(secretsPath)
+			if err != nil {
+				return err
+			}
+			for _, p := range problems {
+				fmt.Printf("%s %s\n", color.YellowString("loose permissions:"), p)
+			}
+			if len(problems) != 0 {
+				return fmt.Errorf("%d permission problem(s) in %s, run jaws doctor --fix-perms", len(problems), secretsPath)
+			}
+			fmt.Printf("%s %s\n", secretsPath, color.GreenString("ok"))
+			return nil
+		},
+	}
+
+	// dockerCredentialHelperCmd represents the docker credential helper command
+	dockerCredentialHelperCmd = &cobra.Command{
+		Use:   "docker-credential-helper",
+		Short: "docker credential helper backed by the secrets manager, run as docker-credential-jaws",
+		Long: `docker credential helper backed by the secrets manager. Docker runs docker-credential-<name> with
+get, store, erase or list and exchanges the request and response on stdin and stdout, add a
+docker-credential-jaws script that runs jaws docker-credential-helper "$@" to your PATH and set
+"credsStore": "jaws" in ~/.docker/config.json.`,
+		Example:   "echo https://index.docker.io/v1/ | jaws docker-credential-helper get",
+		Args:      cobra.ExactValidArgs(1),
+		ValidArgs: []string{"get", "store", "erase", "list"},
+		RunE: func(cmd *cobra.Command, args []string) error {
+			// store and erase change the secrets manager, get and list only read it
+			if args[0] == "store" || args[0] == "erase" {
+				if err := helpers.CheckProfile(secretManager.ProfileName(), confirmProd); err != nil {
+					return err
+				}
+			}
+			return secretsmanager.DockerCredentialHelper(secretManager, args[0], dockerPrefix, os.Stdin, os.Stdout)
+		},
+	}
+
 	// getCmd represents the set command
 	getCmd = &cobra.Command{
 		Use:   "get",
@@ -194,24 +477,58 @@ secrets they will create a path using the name of the secret, it requires the sa
 		Long: `download or print secret from aws, if no secret is specified jaws loads the list of secrets into
 fzf, you can then search for secrets by typing, select secrets with tab and enter to confirm
 selected secrets to download them.`,
-		Example: "jaws get testing/app/default/key -p",
-		Aliases: []string{"g"},
+		Example:           "jaws get testing/app/default/key -p",
+		Aliases:           []string{"g"},
+		ValidArgsFunction: completeSecretIDs,
 		RunE: func(cmd *cobra.Command, args []string) error {
 			var noSelErr = errors.New("no secrets selected")
 			var secretIDs []string
+			args, err := helpers.StdinArgs(args)
+			if err != nil {
+				return err
+			}
+			secretManager.SetFilter(secretsmanager.Filter{Prefix: prefixFilter, Tags: tagFilter})
 			Secrets, err := secretManager.Get(args)
 			if err != nil {
 				return err
 			}
+			if len(args) != 0 && len(Secrets) == 0 {
+				return secretsmanager.ErrNotFound
+			}
 
 			if !formatPrintValue && !cleanPrintValue {
 				for _, s := range Secrets {
-					err = secretsmanager.DownloadSecret(s.ID, s.Content, secretsPath)
+					if s.Key != "" {
+						return fmt.Errorf("%s#%s: a single key can only be printed, add --print", s.ID, s.Key)
+					}
+				}
+				type downloadedSecret struct {
+					ID      string `json:"id" yaml:"id"`
+					Path    string `json:"path" yaml:"path"`
+					Changed bool   `json:"changed" yaml:"changed"`
+				}
+				var downloaded []downloadedSecret
+				var changedIDs []string
+				downloadedTable := helpers.Table{Header: []string{"id", "path", "changed"}}
+				for _, s := range Secrets {
+					changed, err := secretsmanager.DownloadSecret(s.ID, s.Content, secretsPath)
 					if err != nil {
 						return err
 					}
 					secretIDs = append(secretIDs, s.ID)
-					fmt.Printf("%s/%s\n", secretsPath, s.ID)
+					if changed {
+						changedIDs = append(changedIDs, s.ID)
+					}
+					path := fmt.Sprintf("%s/%s", secretsPath, s.ID)
+					if outputFormat == helpers.OutputTable {
+						if changed {
+							fmt.Println(path)
+						} else {
+							fmt.Printf("%s %s\n", path, color.YellowString("unchanged"))
+						}
+					}
+					downloaded = append(downloaded, downloadedSecret{ID: s.ID, Path: path, Changed: changed})
+					downloadedTable.AddRow(s.ID, path, fmt.Sprint(changed))
 				}
 				f, err := filepath.Abs(secretsPath)
 				if err != nil {
@@ -220,7 +537,13 @@ selected secrets to download them.`,
 				baseOfPath := fmt.Sprintf("/%s", filepath.Base(f))
 				parentPath := strings.TrimSuffix(f, baseOfPath)
 				_ = helpers.CheckIfGitRepo(parentPath, true)
-				helpers.GitControlSecrets(secretIDs, secretsPath)
+				if !noVCS && len(changedIDs) != 0 {
+					helpers.GitControlSecrets(helpers.Snapshot{
+						Command:   "get",
+						Profile:   secretManager.ProfileName(),
+						SecretIDs: changedIDs,
+					}, secretsPath)
+				}
 				if useEditor {
 					if err = helpers.OpenEditor(secretIDs, secretsPath); err != nil {
 						if err.Error() != noSelErr.Error() {
@@ -228,28 +551,252 @@ selected secrets to download them.`,
 						}
 					}
 				}
-			} else {
-				if cleanPrintValue {
-					secretsmanager.CleanPrintSecrets(Secrets)
-				} else if formatPrintValue {
-					secretsmanager.FormatPrintSecret(Secrets)
+				if outputFormat != helpers.OutputTable {
+					if err = helpers.PrintOutput(outputFormat, downloaded, downloadedTable); err != nil {
+						return err
+					}
+				}
+			} else if outputFormat != helpers.OutputTable {
+				t := helpers.Table{Header: []string{"id", "key", "content", "binary"}}
+				for _, s := range Secrets {
+					t.AddRow(s.ID, s.Key, s.Content, fmt.Sprint(s.Binary))
 				}
+				if err = helpers.PrintOutput(outputFormat, Secrets, t); err != nil {
+					return err
+				}
+			} else if cleanPrintValue {
+				secretsmanager.CleanPrintSecrets(Secrets)
+			} else if formatPrintValue {
+				secretsmanager.FormatPrintSecret(Secrets)
+			}
+			if len(Secrets) < len(args) {
+				return &secretsmanager.PartialFailure{Done: len(Secrets), Err: secretsmanager.ErrNotFound}
 			}
 			return nil
 		},
 	}
 
+	// explainCmd represents the explain command
+	explainCmd = &cobra.Command{
+		Use:     "explain",
+		Short:   "explain the cause and fix of an error code, lists every code if none is given",
+		Example: "jaws explain E004",
+		Args:    cobra.MaximumNArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			if len(args) == 0 {
+				for _, info := range secretsmanager.ErrorCatalog() {
+					fmt.Printf("%s %s\n", color.YellowString(info.Code), info.Title)
+				}
+				return nil
+			}
+			info, ok := secretsmanager.Explain(args[0])
+			if !ok {
+				return fmt.Errorf("unknown error code %s, run jaws explain to list them", args[0])
+			}
+			fmt.Printf("%s %s\n", color.YellowString(info.Code), info.Title)
+			fmt.Printf("cause: %s\n", info.Cause)
+			fmt.Printf("fix:   %s\n", info.Fix)
+			return nil
+		},
+	}
+
+	// historyCmd represents the history command
+	historyCmd = &cobra.Command{
+		Use:               "history",
+		Short:             "show when a secret changed and by which commit",
+		Example:           "jaws history testing/app/default/secret --remote",
+		Args:              cobra.ExactArgs(1),
+		ValidArgsFunction: completeSecretID,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			var history secretHistory
+			if historyRemote {
+				info, err := secretManager.Describe(args[0])
+				if err != nil {
+					return err
+				}
+				history.Secret = &info
+				if history.Versions, err = secretManager.History(args[0]); err != nil {
+					return err
+				}
+			}
+			if !historyRemote || historyVCS {
+				if err := requireVCS(cmd, args); err != nil {
+					return err
+				}
+				commits, err := helpers.GitFileHistoryEntries(secretsPath, args[0])
+				if err != nil {
+					return err
+				}
+				history.Commits = commits
+			}
+
+			if outputFormat == helpers.OutputTable && history.Secret != nil {
+				secretsmanager.PrintRotation(*history.Secret)
+			}
+			return helpers.PrintOutput(outputFormat, history, history.table())
+		},
+	}
+
+	// k8sSyncCmd represents the k8s-sync command
+	k8sSyncCmd = &cobra.Command{
+		Use:   "k8s-sync",
+		Short: "render secrets into kubernetes secret manifests, or apply them with kubectl",
+		Long: `render secrets into kubernetes secret manifests, or apply them with kubectl. The mapping file lists
+each kubernetes secret with the jaws secret it comes from, a json object is split into one key per field:
+
+secrets:
+  - name: app-db
+    from: testing/app/default/db
+  - name: app-cert
+    from: testing/app/default/cert
+    key: tls.crt`,
+		Example: "jaws k8s-sync --namespace app --map mapping.yaml --apply --watch 5m",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			return secretsmanager.K8sSync(secretManager, secretsmanager.K8sSyncOptions{
+				MapFile:   k8sMapFile,
+				Namespace: k8sNamespace,
+				Apply:     k8sApply,
+				Watch:     k8sWatch,
+			}, os.Stdout)
+		},
+	}
+
 	// listCmd represents the list command
 	listCmd = &cobra.Command{
 		Use:     "list",
 		Short:   "list available secrets",
 		Aliases: []string{"ls"},
 		RunE: func(cmd *cobra.Command, args []string) error {
+			secretManager.SetFilter(secretsmanager.Filter{Prefix: prefixFilter, Tags: tagFilter})
+			if longList {
+				details, err := secretManager.ListDetails()
+				if err != nil {
+					return err
+				}
+				return helpers.PrintOutput(outputFormat, details, secretsmanager.SecretInfoTable(details))
+			}
 			list, err := secretManager.ListAll()
+			if err != nil {
+				return err
+			}
+			var t helpers.Table
 			for _, secretID := range list {
-				fmt.Println(secretID)
+				t.AddRow(secretID)
 			}
-			return err
+			return helpers.PrintOutput(outputFormat, list, t)
+		},
+	}
+
+	// logCmd represents the log command
+	logCmd = &cobra.Command{
+		Use:     "log",
+		Short:   "show the history of the secrets folder, use --diff to see what a commit changed",
+		Example: "jaws log --diff HEAD --mask",
+		PreRunE: requireVCS,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			if logDiffRev != "" {
+				if outputFormat != helpers.OutputTable {
+					return errors.New("--diff only supports table output")
+				}
+				return helpers.GitLogDiff(secretsPath, logDiffRev, maskValues)
+			}
+			entries, err := helpers.GitLogEntries(secretsPath)
+			if err != nil {
+				return err
+			}
+			return helpers.PrintOutput(outputFormat, entries, helpers.LogTable(entries))
+		},
+	}
+
+	// policyCmd represents the policy command
+	policyCmd = &cobra.Command{
+		Use:   "policy",
+		Short: "review and update the resource policy of a secret",
+	}
+
+	// policyShowCmd represents the policy show command
+	policyShowCmd = &cobra.Command{
+		Use:               "show",
+		Short:             "print the resource policy of a secret",
+		Example:           "jaws policy show testing/app/default/secret",
+		Aliases:           []string{"get"},
+		Args:              cobra.ExactArgs(1),
+		ValidArgsFunction: completeSecretID,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			policy, err := secretManager.GetPolicy(args[0])
+			if err != nil {
+				return err
+			}
+			fmt.Println(policy)
+			return nil
+		},
+	}
+
+	// policySetCmd represents the policy set command
+	policySetCmd = &cobra.Command{
+		Use:               "set",
+		Short:             "replace the resource policy of a secret from a json file, public policies are blocked",
+		Example:           "jaws policy set testing/app/default/secret --file policy.json",
+		Args:              cobra.ExactArgs(1),
+		ValidArgsFunction: completeSecretID,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			return secretManager.SetPolicy(args[0], policyFile)
+		},
+	}
+
+	// resolveCmd represents the resolve command
+	resolveCmd = &cobra.Command{
+		Use:   "resolve",
+		Short: "print the values of several secrets as one flat object keyed by ref, never prompts",
+		Long: `print the values of several secrets as one flat object keyed by ref, never prompts. Use --output json
+for terraform external data sources and other automation, a ref that does not resolve fails the whole call.`,
+		Example:           "jaws resolve --output json testing/app/default/db testing/app/default/key@AWSPREVIOUS",
+		Args:              cobra.MinimumNArgs(1),
+		ValidArgsFunction: completeSecretIDs,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			helpers.SetNonInteractive(true)
+			refs, err := helpers.StdinArgs(args)
+			if err != nil {
+				return err
+			}
+			secrets, err := secretManager.Get(refs)
+			if err != nil {
+				return err
+			}
+			// Get keeps the order of the refs and skips missing secrets
+			if len(secrets) != len(refs) {
+				var missing []string
+				for i, j := 0, 0; i < len(refs); i++ {
+					if j < len(secrets) && secretsmanager.RefSecretID(refs[i]) == secrets[j].ID {
+						j++
+						continue
+					}
+					missing = append(missing, refs[i])
+				}
+				return fmt.Errorf("resolving %s: %w", strings.Join(missing, ", "), secretsmanager.ErrNotFound)
+			}
+			values := map[string]string{}
+			t := helpers.Table{Header: []string{"ref", "value"}}
+			for i, s := range secrets {
+				values[refs[i]] = s.Content
+				t.AddRow(refs[i], s.Content)
+			}
+			return helpers.PrintOutput(outputFormat, values, t)
+		},
+	}
+
+	// replicateCmd represents the replicate command
+	replicateCmd = &cobra.Command{
+		Use:               "replicate",
+		Short:             "replicate existing secrets to other regions, if no secret is specified use fzf to select secret(s)",
+		Example:           "jaws replicate testing/app/default/secret --regions eu-west-1",
+		ValidArgsFunction: completeSecretIDs,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			args, err := helpers.StdinArgs(args)
+			if err != nil {
+				return err
+			}
+			return secretManager.Replicate(args, replicaRegions)
 		},
 	}
 
@@ -265,14 +812,164 @@ selected secrets to download them.`,
 
 	// setCmd represents the set command
 	setCmd = &cobra.Command{
-		Use:     "set",
-		Short:   "updates secrets and will prompt to create if there is a new secret detected",
+		Use:   "set",
+		Short: "updates secrets and will prompt to create if there is a new secret detected",
+		Long: `updates secrets and will prompt to create if there is a new secret detected. Given a secret id and
+key.path=value pairs, set patches just those keys of the json secret and pushes the merged document.`,
+		Example: "jaws set testing/app/default/config database.password=hunter22",
 		Aliases: []string{"s"},
+		PreRunE: func(cmd *cobra.Command, args []string) error {
+			if len(args) != 0 {
+				return nil
+			}
+			return secretsmanager.SetPreRun(secretsPath, validators, noVCS)
+		},
 		RunE: func(cmd *cobra.Command, args []string) error {
-			return secretManager.Set(secretsPath, createPrompt)
+			if len(args) == 1 {
+				return errors.New("add key.path=value pairs to patch the secret")
+			} else if len(args) > 1 {
+				result, err := secretsmanager.PatchKeys(secretManager, args[0], args[1:], assumeYes)
+				if err != nil {
+					return err
+				}
+				results := []secretsmanager.SetResult{result}
+				if outputFormat == helpers.OutputTable {
+					secretsmanager.PrintSetResults(results)
+					return nil
+				}
+				return helpers.PrintOutput(outputFormat, results, secretsmanager.SetResultsTable(results))
+			}
+			results, err := secretManager.Set(secretsPath, secretsmanager.SetOptions{
+				SkipCreatePrompt: createPrompt,
+				KMSKeyID:         kmsKeyID,
+				Yes:              assumeYes,
+			})
+			// only what reached the secrets manager goes into the history, also after a partial failure
+			if commitErr := secretsmanager.SetCommit(secretsPath, secretManager.ProfileName(), results, noVCS); commitErr != nil && err == nil {
+				err = commitErr
+			}
+			if outputFormat == helpers.OutputTable {
+				secretsmanager.PrintSetResults(results)
+			} else if printErr := helpers.PrintOutput(outputFormat, results, secretsmanager.SetResultsTable(results)); printErr != nil {
+				return printErr
+			}
+			if err != nil && len(results) != 0 {
+				return &secretsmanager.PartialFailure{Done: len(results), Err: err}
+			}
+			return err
 		},
 		PostRunE: func(cmd *cobra.Command, args []string) error {
-			return secretsmanager.SetPostRun(secretsPath, cleanLocalSecrets)
+			if len(args) != 0 {
+				return nil
+			}
+			return secretsmanager.SetPostRun(secretsPath, secretManager.ProfileName(), cleanLocalSecrets, noVCS)
+		},
+	}
+
+	// vcsCmd represents the vcs command
+	vcsCmd = &cobra.Command{
+		Use:               "vcs",
+		Short:             "manage the git history kept in the secrets folder",
+		PersistentPreRunE: requireVCS,
+	}
+
+	// vcsPushCmd represents the vcs push command
+	vcsPushCmd = &cobra.Command{
+		Use:     "push",
+		Short:   "push the secrets folder history to the vcs_remote set in the config",
+		Example: "jaws vcs push",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			return helpers.GitPush(secretsPath, vcsRemote, secretManager.ProfileName())
+		},
+	}
+
+	// vcsGCCmd represents the vcs gc command
+	vcsGCCmd = &cobra.Command{
+		Use:     "gc",
+		Short:   "squash old commits in the secrets folder and prune objects that are no longer needed",
+		Example: "jaws vcs gc --days 30",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			return helpers.GitGC(secretsPath, gcKeepDays)
+		},
+	}
+
+	// vcsExportGitCmd represents the vcs export-git command
+	vcsExportGitCmd = &cobra.Command{
+		Use:     "export-git",
+		Short:   "copy the secrets folder history into a new bare git repository",
+		Example: "jaws vcs export-git ~/backups/secrets.git",
+		Args:    cobra.ExactArgs(1),
+		RunE: func(cmd *cobra.Command, args []string) error {
+			return helpers.GitExport(secretsPath, args[0])
+		},
+	}
+
+	// agentCmd represents the agent command
+	agentCmd = &cobra.Command{
+		Use:   "agent",
+		Short: "run jaws watch in the background as a systemd user service, or a launchd agent on macOS",
+	}
+
+	// agentInstallCmd represents the agent install command
+	agentInstallCmd = &cobra.Command{
+		Use:     "install",
+		Short:   "install and start the agent for the current secrets folder and config",
+		Example: "jaws agent install --interval 5s",
+		PreRunE: requireVCS,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			exe, err := os.Executable()
+			if err != nil {
+				return err
+			}
+			path, err := filepath.Abs(secretsPath)
+			if err != nil {
+				return err
+			}
+			opts := helpers.AgentOptions{
+				Executable: exe,
+				Args:       []string{"watch", "--path", path, "--interval", watchInterval.String()},
+				Env:        map[string]string{"PATH": os.Getenv("PATH")},
+			}
+			if cfgFile != "" {
+				cfg, err := filepath.Abs(helpers.ExpandHome(cfgFile))
+				if err != nil {
+					return err
+				}
+				opts.Args = append(opts.Args, "--config", cfg)
+			}
+			if editor := os.Getenv("EDITOR"); editor != "" {
+				opts.Env["EDITOR"] = editor
+			}
+			return helpers.InstallAgent(opts)
+		},
+	}
+
+	// agentUninstallCmd represents the agent uninstall command
+	agentUninstallCmd = &cobra.Command{
+		Use:   "uninstall",
+		Short: "stop the agent and remove its service",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			return helpers.UninstallAgent()
+		},
+	}
+
+	// agentStatusCmd represents the agent status command
+	agentStatusCmd = &cobra.Command{
+		Use:   "status",
+		Short: "show whether the agent is installed and running",
+		RunE: func(cmd *cobra.Command, args []string) error {
+			return helpers.AgentStatus()
+		},
+	}
+
+	// watchCmd represents the watch command
+	watchCmd = &cobra.Command{
+		Use:     "watch",
+		Short:   "commit secrets to the secrets folder history whenever they are saved",
+		Example: "jaws watch --interval 5s",
+		PreRunE: requireVCS,
+		RunE: func(cmd *cobra.Command, args []string) error {
+			return helpers.WatchSecrets(secretsPath, watchInterval, secretManager.ProfileName(), validators)
 		},
 	}
 
@@ -312,41 +1009,134 @@ func init() {
 	flags()
 }
 
+// requireConfig stops commands that need a manager when the config could not be read, the error is
+// returned to main so it gets its code and exit code
+func requireConfig(cmd *cobra.Command, args []string) error {
+	if configErr != nil && !configOptional(cmd) {
+		return configErr
+	}
+	return nil
+}
+
+// configOptional reports whether cmd runs without a config, e.g. to create one
+func configOptional(cmd *cobra.Command) bool {
+	switch cmd {
+	case versionCmd, pathCmd, pathCommandCmd, explainCmd, configCreateCmd, doctorCmd:
+		return true
+	}
+	// help and the shell completion commands added by cobra
+	switch cmd.Name() {
+	case "help", cobra.ShellCompRequestCmd, cobra.ShellCompNoDescRequestCmd:
+		return true
+	}
+	return cmd.HasParent() && cmd.Parent().Name() == "completion"
+}
+
+// requireVCS stops commands that need the secrets folder history when it is disabled
+func requireVCS(cmd *cobra.Command, args []string) error {
+	if err := requireConfig(cmd, args); err != nil {
+		return err
+	}
+	if noVCS {
+		return secretsmanager.ErrVCSDisabled
+	}
+	return nil
+}
+
+// completeSecretIDs offers the secret ids of the default profile that start with the typed text as shell completions
+func completeSecretIDs(cmd *cobra.Command, args []string, toComplete string) ([]string, cobra.ShellCompDirective) {
+	if secretManager == nil {
+		return nil, cobra.ShellCompDirectiveNoFileComp
+	}
+	secretManager.SetFilter(secretsmanager.Filter{Prefix: toComplete})
+	ids, err := secretManager.ListAll()
+	if err != nil {
+		return nil, cobra.ShellCompDirectiveError
+	}
+	return ids, cobra.ShellCompDirectiveNoFileComp
+}
+
+// completeSecretID completes the secret id of commands that take exactly one
+func completeSecretID(cmd *cobra.Command, args []string, toComplete string) ([]string, cobra.ShellCompDirective) {
+	if len(args) != 0 {
+		return nil, cobra.ShellCompDirectiveNoFileComp
+	}
+	return completeSecretIDs(cmd, args, toComplete)
+}
+
+// secretHistory is the output of the history command
+type secretHistory struct {
+	Secret   *secretsmanager.SecretInfo     `json:"secret,omitempty" yaml:"secret,omitempty"`
+	Versions []secretsmanager.SecretVersion `json:"versions,omitempty" yaml:"versions,omitempty"`
+	Commits  []helpers.LogEntry             `json:"commits,omitempty" yaml:"commits,omitempty"`
+}
+
+// table lays out the remote versions and local commits of a secret as one list, remote versions first
+func (h secretHistory) table() helpers.Table {
+	t := helpers.Table{Header: []string{"source", "id", "date", "detail"}}
+	for _, v := range h.Versions {
+		t.AddRow("remote", v.ID, v.Created.Format("2006-01-02 15:04:05"), strings.Join(v.Stages, ","))
+	}
+	for _, c := range h.Commits {
+		t.AddRow("vcs", c.Hash[:8], c.Date.Format("2006-01-02 15:04:05"), c.Summary)
+	}
+	return t
+}
+
 // initConfig reads in config file and ENV variables if set.
 func initConfig() {
+	if err := helpers.CheckOutputFormat(outputFormat); err != nil {
+		configErr = err
+		return
+	}
+	ciMode = helpers.CIEnabled(ciMode)
+	helpers.SetNonInteractive(nonInteractive || ciMode)
+	if ciMode {
+		// only the command path, arguments like jaws set id key=value can hold secret values
+		title := rootCmd.Name()
+		if cmd, _, err := rootCmd.Find(os.Args[1:]); err == nil {
+			title = cmd.CommandPath()
+		}
+		helpers.StartCIGroup(title)
+	}
+	if quiet {
+		devNull, err := os.OpenFile(os.DevNull, os.O_WRONLY, 0)
+		if err != nil {
+			configErr = err
+			return
+		}
+		os.Stdout = devNull
+		color.Output = devNull
+	}
 	jawsConf = secretsmanager.InitJawsConfig()
 
 	if cfgFile != "" {
-		jawsConf.SetConfigName(cfgFile)
+		jawsConf.SetConfigName(helpers.ExpandHome(cfgFile))
 	} else {
 		jawsConf.SetConfigName("jaws.conf")
 		jawsConf.AddConfigPath(".")
-		jawsConf.AddConfigPath(fmt.Sprintf("%s/.jaws", os.Getenv("HOME")))
-		jawsConf.AddConfigPath(fmt.Sprintf("%s/.config/jaws", os.Getenv("HOME")))
+		jawsConf.AddConfigPath(helpers.ExpandHome("~/.jaws"))
+		jawsConf.AddConfigPath(helpers.ExpandHome("~/.config/jaws"))
 	}
 
 	general, managers, err := jawsConf.ReadInConfig()
 	if err != nil {
 		switch err.(type) {
 		case *secretsmanager.NoConfigFileFound:
-			fmt.Println("no config found, defaulting to aws")
-			secretManager = &secretsmanager.AWSManager{
-				Profile: "default",
-			}
-			general = secretsmanager.GeneralHCL{
-				DefaultProfile: "default",
-			}
+			// commands that run without a config still get the default secrets folder
+			configErr = err
 		case *secretsmanager.DecodeConfigFailed:
-			secretManager = &secretsmanager.AWSManager{
-				Profile: "default",
-			}
-			general = secretsmanager.GeneralHCL{
-				DefaultProfile: "default",
-			}
+			// no fallback, a config that fails to decode would drop its policy block and protected profiles
+			configErr = err
+			return
 		default:
-			log.Fatalln(err)
+			configErr = err
+			return
 		}
 	} else {
+		if profileName != "" {
+			general.DefaultProfile = profileName
+		}
 		if len(managers) != 0 {
 			for _, m := range managers {
 				if m.ProfileName() == general.DefaultProfile {
@@ -354,6 +1144,10 @@ func initConfig() {
 				}
 			}
 		}
+		if secretManager == nil {
+			configErr = fmt.Errorf("no manager block for profile %s in the config, check --profile and default_profile", general.DefaultProfile)
+			return
+		}
 	}
 
 	// check if secretsPath flag is set to something other than secrets, if not then use config set path
@@ -364,7 +1158,50 @@ func initConfig() {
 			secretsPath = general.SecretsPath
 		}
 	}
+	secretsPath = helpers.ExpandHome(secretsPath)
+	// keep the secrets folder private and warn when a sync client uploads it
+	if err := helpers.RestrictSecretsDir(secretsPath); err != nil {
+		fmt.Fprintf(os.Stderr, "%s %v\n", color.YellowString("restricting secrets folder:"), err)
+	}
+	if dir := helpers.CloudSynced(secretsPath); dir != "" {
+		fmt.Fprintf(os.Stderr, "%s %s is inside %s, run jaws doctor for details\n", color.YellowString("warning:"), secretsPath, dir)
+	}
 	if general.Editor != "" {
 		os.Setenv("EDITOR", general.Editor)
 	}
+	if general.VCS != nil && !*general.VCS {
+		noVCS = true
+	}
+	vcsRemote = general.VCSRemote
+	if general.Finder != nil && secretManager != nil {
+		secretManager.SetFinder(secretsmanager.FinderOptions{
+			Prompt:       general.Finder.Prompt,
+			SingleSelect: general.Finder.MultiSelect != nil && !*general.Finder.MultiSelect,
+		})
+	}
+	if ciMode && secretManager != nil {
+		secretManager.SetCI()
+	}
+	if general.Concurrency != 0 && secretManager != nil {
+		secretManager.SetConcurrency(general.Concurrency)
+	}
+	if general.Policy != nil {
+		err = helpers.SetPolicy(helpers.Policy{
+			ProtectedProfiles: general.Policy.ProtectedProfiles,
+			MinValueLength:    general.Policy.MinValueLength,
+			DenyValues:        general.Policy.DenyValues,
+			MaxRotationAge:    time.Duration(general.Policy.MaxRotationAgeDays) * 24 * time.Hour,
+		})
+		if err != nil {
+			configErr = err
+			return
+		}
+	}
+	if general.Validate != nil {
+		validators = helpers.Validators{
+			JSON:               general.Validate.JSON,
+			TrailingWhitespace: general.Validate.TrailingWhitespace,
+			MaxSize:            general.Validate.MaxSize,
+		}
+	}
 }
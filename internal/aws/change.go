@@ -4,12 +4,11 @@ import (
 	"context"
 	"errors"
 	"fmt"
-	"strings"
 	"time"
 
 	"github.com/aws/aws-sdk-go-v2/aws"
 	"github.com/aws/aws-sdk-go-v2/service/secretsmanager"
-	"github.com/aws/aws-sdk-go-v2/service/sso/types"
+	"github.com/aws/aws-sdk-go-v2/service/secretsmanager/types"
 	"github.com/fatih/color"
 	"github.com/google/uuid"
 )
@@ -23,16 +22,23 @@ func UpdateSecretString(ctx context.Context, client *secretsmanager.Client, secr
 		SecretString:       aws.String(secretString),
 	}
 	_, err := client.UpdateSecret(ctx, updateSecretInput)
-	if err != nil {
-		return err
-	}
-	fmt.Printf("%s %s\n", secretID, color.YellowString("updated"))
+	return err
+}
 
-	return nil
+func UpdateSecretBinary(ctx context.Context, client *secretsmanager.Client, secretID string, secretBinary []byte) error {
+	newVersionID := uuid.New()
+
+	updateSecretInput := &secretsmanager.UpdateSecretInput{
+		SecretId:           aws.String(secretID),
+		ClientRequestToken: aws.String(newVersionID.String()),
+		SecretBinary:       secretBinary,
+	}
+	_, err := client.UpdateSecret(ctx, updateSecretInput)
+	return err
 }
 
 func RollbackSecret(ctx context.Context, client *secretsmanager.Client, secretID string) error {
-	timeCtx, cancel := context.WithTimeout(ctx, 5*time.Second)
+	timeCtx, cancel := callContext(ctx, 5*time.Second)
 	defer cancel()
 	listVerionInput := &secretsmanager.ListSecretVersionIdsInput{
 		SecretId: aws.String(secretID),
@@ -66,8 +72,10 @@ func RollbackSecret(ctx context.Context, client *secretsmanager.Client, secretID
 	return nil
 }
 
-func CheckIfUpdate(ctx context.Context, client *secretsmanager.Client, secretID string, updatedString string) (bool, error) {
-	timeCtx, cancel := context.WithTimeout(ctx, 2*time.Second)
+// GetRemoteValue fetches the current value of a secret so it can be compared with the local file before pushing,
+// exists is false for secrets that still have to be created
+func GetRemoteValue(ctx context.Context, client *secretsmanager.Client, secretID string) (content []byte, binary bool, exists bool, err error) {
+	timeCtx, cancel := callContext(ctx, 2*time.Second)
 	defer cancel()
 
 	var rnfErr *types.ResourceNotFoundException
@@ -77,15 +85,13 @@ func CheckIfUpdate(ctx context.Context, client *secretsmanager.Client, secretID
 
 	secretValueOutput, err := client.GetSecretValue(timeCtx, getSecretValueInput)
 	if err != nil {
-		if !errors.As(err, &rnfErr) {
-			return true, nil
-		} else {
-			return false, err
+		if errors.As(err, &rnfErr) {
+			return nil, false, false, nil
 		}
+		return nil, false, false, err
 	}
-	diffCheck := strings.Compare(*secretValueOutput.SecretString, updatedString)
-	if diffCheck == 0 {
-		return false, nil
+	if secretValueOutput.SecretString == nil {
+		return secretValueOutput.SecretBinary, true, true, nil
 	}
-	return true, nil
+	return []byte(*secretValueOutput.SecretString), false, true, nil
 }
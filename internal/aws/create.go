@@ -10,12 +10,19 @@ import (
 	"github.com/aws/aws-sdk-go-v2/aws"
 	"github.com/aws/aws-sdk-go-v2/service/secretsmanager"
 	"github.com/aws/aws-sdk-go-v2/service/secretsmanager/types"
-	"github.com/fatih/color"
 	"github.com/google/uuid"
+	"github.com/jacbart/jaws/utils/helpers"
 )
 
-func CreateSecret(ctx context.Context, client *secretsmanager.Client, secretID string, secretString string) error {
-	timeCtx, cancel := context.WithTimeout(ctx, 2*time.Second)
+// CreateOptions are the settings applied to newly created secrets, Binary stores the value as SecretBinary
+type CreateOptions struct {
+	KMSKeyID       string
+	ReplicaRegions []string
+	Binary         bool
+}
+
+func CreateSecret(ctx context.Context, client *secretsmanager.Client, secretID string, secretString string, opts CreateOptions) error {
+	timeCtx, cancel := callContext(ctx, 2*time.Second)
 	defer cancel()
 	newRequestToken := uuid.New()
 
@@ -24,42 +31,62 @@ func CreateSecret(ctx context.Context, client *secretsmanager.Client, secretID s
 		ClientRequestToken: aws.String(newRequestToken.String()),
 		SecretString:       aws.String(secretString),
 	}
+	if opts.Binary {
+		createSecretInput.SecretString = nil
+		createSecretInput.SecretBinary = []byte(secretString)
+	}
+	if opts.KMSKeyID != "" {
+		createSecretInput.KmsKeyId = aws.String(opts.KMSKeyID)
+	}
+	if len(opts.ReplicaRegions) != 0 {
+		createSecretInput.AddReplicaRegions = replicaRegions(opts.ReplicaRegions)
+	}
 
 	_, err := client.CreateSecret(timeCtx, createSecretInput)
-	if err != nil {
-		return err
-	}
-	fmt.Printf("%s %s\n", secretID, color.MagentaString("created"))
-	return nil
+	return err
 }
 
-func HandleUpdateCreate(ctx context.Context, client *secretsmanager.Client, secretID string, secretString string, createPrompt bool) error {
+// Actions reported by HandleUpdateCreate for each pushed secret
+const (
+	ActionUpdated = "updated"
+	ActionCreated = "created"
+	ActionSkipped = "skipped"
+)
+
+// HandleUpdateCreate updates a secret, or creates it when it does not exist yet, and returns what was done
+func HandleUpdateCreate(ctx context.Context, client *secretsmanager.Client, secretID string, secretString string, createPrompt bool, opts CreateOptions) (string, error) {
 	var userResponse string
 	var rnfErr *types.ResourceNotFoundException
-	if err := UpdateSecretString(ctx, client, secretID, string(secretString)); err != nil {
-		if errors.As(err, &rnfErr) {
-			if !createPrompt {
-				fmt.Printf("%s was not found, would you like to create this secret? [y/N] ", secretID)
-				fmt.Scanln(&userResponse)
+	var err error
+	if opts.Binary {
+		err = UpdateSecretBinary(ctx, client, secretID, []byte(secretString))
+	} else {
+		err = UpdateSecretString(ctx, client, secretID, secretString)
+	}
+	if err == nil {
+		return ActionUpdated, nil
+	}
+	if !errors.As(err, &rnfErr) {
+		return "", err
+	}
+	if !createPrompt {
+		if err = helpers.RequireInteractive(fmt.Sprintf("creating %s", secretID), "pass --no-prompt to create new secrets"); err != nil {
+			return "", err
+		}
+		userResponse, err = helpers.Prompt(fmt.Sprintf("%s was not found, would you like to create this secret? [y/N] ", secretID))
+		if err != nil {
+			return "", err
+		}
 
-				userResponse = strings.TrimSpace(userResponse)
-				userResponse = strings.ToLower(userResponse)
+		userResponse = strings.TrimSpace(userResponse)
+		userResponse = strings.ToLower(userResponse)
 
-				if userResponse == "y" || userResponse == "yes" {
-					if err = CreateSecret(ctx, client, secretID, string(secretString)); err != nil {
-						return err
-					}
-				} else {
-					fmt.Printf("creation of %s %s\n", secretID, color.CyanString("skipped"))
-				}
-			} else {
-				if err = CreateSecret(ctx, client, secretID, string(secretString)); err != nil {
-					return err
-				}
-			}
-		} else {
-			return err
+		if userResponse != "y" && userResponse != "yes" {
+			return ActionSkipped, nil
 		}
 	}
-	return nil
+	if err = CreateSecret(ctx, client, secretID, secretString, opts); err != nil {
+		return "", err
+	}
+	return ActionCreated, nil
 }
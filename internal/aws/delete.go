@@ -11,7 +11,7 @@ import (
 )
 
 func ScheduleDeletion(ctx context.Context, client *secretsmanager.Client, secretID string, recoveryWindow int64) error {
-	timeCtx, cancel := context.WithTimeout(ctx, 2*time.Second)
+	timeCtx, cancel := callContext(ctx, 2*time.Second)
 	defer cancel()
 	deleteSecretInput := &secretsmanager.DeleteSecretInput{
 		SecretId:                   aws.String(secretID),
@@ -27,8 +27,19 @@ func ScheduleDeletion(ctx context.Context, client *secretsmanager.Client, secret
 	return nil
 }
 
+// ForceDelete deletes a secret right away, it can not be restored
+func ForceDelete(ctx context.Context, client *secretsmanager.Client, secretID string) error {
+	timeCtx, cancel := callContext(ctx, 2*time.Second)
+	defer cancel()
+	_, err := client.DeleteSecret(timeCtx, &secretsmanager.DeleteSecretInput{
+		SecretId:                   aws.String(secretID),
+		ForceDeleteWithoutRecovery: true,
+	})
+	return err
+}
+
 func CancelDeletion(ctx context.Context, client *secretsmanager.Client, secretID string) error {
-	timeCtx, cancel := context.WithTimeout(ctx, 2*time.Second)
+	timeCtx, cancel := callContext(ctx, 2*time.Second)
 	defer cancel()
 	restoreSecretInput := &secretsmanager.RestoreSecretInput{
 		SecretId: aws.String(secretID),
@@ -6,14 +6,26 @@ import (
 	"os"
 	"path/filepath"
 	"strings"
+	"time"
 
+	"github.com/aws/aws-sdk-go-v2/aws"
 	"github.com/aws/aws-sdk-go-v2/service/secretsmanager"
+	"github.com/aws/aws-sdk-go-v2/service/secretsmanager/types"
 )
 
-func GetSecretsList(ctx context.Context, client *secretsmanager.Client, nextToken *string) (*secretsmanager.ListSecretsOutput, error) {
+func GetSecretsList(ctx context.Context, client *secretsmanager.Client, nextToken *string, prefix string) (*secretsmanager.ListSecretsOutput, error) {
 	input := &secretsmanager.ListSecretsInput{
 		NextToken: nextToken,
 	}
+	// the name filter is a prefix match done by secretsmanager, so only matching secrets are paged through
+	if prefix != "" {
+		input.Filters = []types.Filter{
+			{
+				Key:    types.FilterNameStringTypeName,
+				Values: []string{prefix},
+			},
+		}
+	}
 	result, err := client.ListSecrets(ctx, input)
 	if err != nil {
 		return nil, err
@@ -45,3 +57,16 @@ func GetSecretNames(secretsPath string) ([]string, error) {
 	}
 	return secretNames, nil
 }
+
+// RotationStatus reports whether secretsmanager rotates the secret with a lambda and when it last did
+func RotationStatus(ctx context.Context, client *secretsmanager.Client, secretID string) (bool, time.Time, error) {
+	timeCtx, cancel := callContext(ctx, 2*time.Second)
+	defer cancel()
+	describeOutput, err := client.DescribeSecret(timeCtx, &secretsmanager.DescribeSecretInput{
+		SecretId: aws.String(secretID),
+	})
+	if err != nil {
+		return false, time.Time{}, err
+	}
+	return describeOutput.RotationEnabled, aws.ToTime(describeOutput.LastRotatedDate), nil
+}
@@ -0,0 +1,42 @@
+package aws
+
+import (
+	"context"
+	"fmt"
+	"time"
+
+	"github.com/aws/aws-sdk-go-v2/aws"
+	"github.com/aws/aws-sdk-go-v2/service/secretsmanager"
+	"github.com/fatih/color"
+)
+
+func GetResourcePolicy(ctx context.Context, client *secretsmanager.Client, secretID string) (string, error) {
+	timeCtx, cancel := callContext(ctx, 2*time.Second)
+	defer cancel()
+	getPolicyInput := &secretsmanager.GetResourcePolicyInput{
+		SecretId: aws.String(secretID),
+	}
+
+	getPolicyOutput, err := client.GetResourcePolicy(timeCtx, getPolicyInput)
+	if err != nil {
+		return "", err
+	}
+	return aws.ToString(getPolicyOutput.ResourcePolicy), nil
+}
+
+func PutResourcePolicy(ctx context.Context, client *secretsmanager.Client, secretID string, policy string) error {
+	timeCtx, cancel := callContext(ctx, 5*time.Second)
+	defer cancel()
+	putPolicyInput := &secretsmanager.PutResourcePolicyInput{
+		SecretId:          aws.String(secretID),
+		ResourcePolicy:    aws.String(policy),
+		BlockPublicPolicy: true,
+	}
+
+	_, err := client.PutResourcePolicy(timeCtx, putPolicyInput)
+	if err != nil {
+		return err
+	}
+	fmt.Printf("%s %s\n", secretID, color.YellowString("policy updated"))
+	return nil
+}
@@ -0,0 +1,41 @@
+package aws
+
+import (
+	"context"
+	"fmt"
+	"time"
+
+	"github.com/aws/aws-sdk-go-v2/aws"
+	"github.com/aws/aws-sdk-go-v2/service/secretsmanager"
+	"github.com/aws/aws-sdk-go-v2/service/secretsmanager/types"
+	"github.com/fatih/color"
+)
+
+func ReplicateSecret(ctx context.Context, client *secretsmanager.Client, secretID string, regions []string) error {
+	timeCtx, cancel := callContext(ctx, 5*time.Second)
+	defer cancel()
+	replicateInput := &secretsmanager.ReplicateSecretToRegionsInput{
+		SecretId:          aws.String(secretID),
+		AddReplicaRegions: replicaRegions(regions),
+	}
+
+	replicateOutput, err := client.ReplicateSecretToRegions(timeCtx, replicateInput)
+	if err != nil {
+		return err
+	}
+	for _, status := range replicateOutput.ReplicationStatus {
+		fmt.Printf("%s %s %s (%s)\n", secretID, color.GreenString("replicating to"), aws.ToString(status.Region), status.Status)
+	}
+	return nil
+}
+
+// replicaRegions builds the replica list for regions, replicas use the default key of their region
+func replicaRegions(regions []string) []types.ReplicaRegionType {
+	var replicas []types.ReplicaRegionType
+	for _, r := range regions {
+		replicas = append(replicas, types.ReplicaRegionType{
+			Region: aws.String(r),
+		})
+	}
+	return replicas
+}
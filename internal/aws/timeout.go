@@ -0,0 +1,21 @@
+package aws
+
+import (
+	"context"
+	"time"
+)
+
+type timeoutKey struct{}
+
+// WithTimeout makes the calls in this package made with ctx use timeout instead of their own default
+func WithTimeout(ctx context.Context, timeout time.Duration) context.Context {
+	return context.WithValue(ctx, timeoutKey{}, timeout)
+}
+
+// callContext bounds a single call by the timeout set with WithTimeout, or by def when none is set
+func callContext(ctx context.Context, def time.Duration) (context.Context, context.CancelFunc) {
+	if timeout, ok := ctx.Value(timeoutKey{}).(time.Duration); ok {
+		return context.WithTimeout(ctx, timeout)
+	}
+	return context.WithTimeout(ctx, def)
+}
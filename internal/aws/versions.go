@@ -0,0 +1,57 @@
+package aws
+
+import (
+	"context"
+	"regexp"
+	"strings"
+	"time"
+
+	"github.com/aws/aws-sdk-go-v2/aws"
+	"github.com/aws/aws-sdk-go-v2/service/secretsmanager"
+	"github.com/aws/aws-sdk-go-v2/service/secretsmanager/types"
+)
+
+var (
+	versionIDPattern    = regexp.MustCompile(`^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$`)
+	versionStagePattern = regexp.MustCompile(`^[A-Z0-9_]+$`)
+)
+
+// SplitVersion splits name@AWSPREVIOUS or name@<version-id> into the secret name, version id and version stage,
+// secret names may contain @ so anything that does not look like a stage or version id is kept in the name
+func SplitVersion(ref string) (string, string, string) {
+	i := strings.LastIndex(ref, "@")
+	if i == -1 {
+		return ref, "", ""
+	}
+	name, suffix := ref[:i], ref[i+1:]
+	switch {
+	case versionIDPattern.MatchString(suffix):
+		return name, suffix, ""
+	case versionStagePattern.MatchString(suffix):
+		return name, "", suffix
+	default:
+		return ref, "", ""
+	}
+}
+
+func ListVersions(ctx context.Context, client *secretsmanager.Client, secretID string) ([]types.SecretVersionsListEntry, error) {
+	timeCtx, cancel := callContext(ctx, 5*time.Second)
+	defer cancel()
+	var versions []types.SecretVersionsListEntry
+	var nextToken *string
+	for {
+		listVersionOutput, err := client.ListSecretVersionIds(timeCtx, &secretsmanager.ListSecretVersionIdsInput{
+			SecretId:          aws.String(secretID),
+			IncludeDeprecated: true,
+			NextToken:         nextToken,
+		})
+		if err != nil {
+			return nil, err
+		}
+		versions = append(versions, listVersionOutput.Versions...)
+		if listVersionOutput.NextToken == nil {
+			return versions, nil
+		}
+		nextToken = listVersionOutput.NextToken
+	}
+}
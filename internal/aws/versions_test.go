@@ -0,0 +1,26 @@
+package aws
+
+import "testing"
+
+func TestSplitVersion(t *testing.T) {
+	tests := []struct {
+		ref     string
+		name    string
+		version string
+		stage   string
+	}{
+		{"prod/app/config", "prod/app/config", "", ""},
+		{"prod/app/config@AWSPREVIOUS", "prod/app/config", "", "AWSPREVIOUS"},
+		{"prod/app/config@AWSCURRENT", "prod/app/config", "", "AWSCURRENT"},
+		{"prod/app/config@0a1b2c3d-4e5f-6a7b-8c9d-0e1f2a3b4c5d", "prod/app/config", "0a1b2c3d-4e5f-6a7b-8c9d-0e1f2a3b4c5d", ""},
+		{"user@example.com", "user@example.com", "", ""},
+		{"team@corp/api@AWSPENDING", "team@corp/api", "", "AWSPENDING"},
+		{"prod/app/config@", "prod/app/config@", "", ""},
+	}
+	for _, tt := range tests {
+		name, version, stage := SplitVersion(tt.ref)
+		if name != tt.name || version != tt.version || stage != tt.stage {
+			t.Errorf("SplitVersion(%q) = %q, %q, %q, want %q, %q, %q", tt.ref, name, version, stage, tt.name, tt.version, tt.stage)
+		}
+	}
+}
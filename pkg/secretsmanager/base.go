@@ -0,0 +1,106 @@
+package secretsmanager
+
+import (
+	"context"
+	"fmt"
+
+	"github.com/jacbart/jaws/utils/helpers"
+)
+
+// baseManager holds the settings every manager gets from the command line and general block, managers
+// embed it and override the operations their platform supports, the rest return ErrUnsupported
+type baseManager struct {
+	Platform    string
+	Profile     string
+	Filter      Filter
+	Finder      FinderOptions
+	Concurrency int
+	CI          bool
+}
+
+// unsupported wraps ErrUnsupported with the platform and operation
+func (b *baseManager) unsupported(operation string) error {
+	return fmt.Errorf("%s %s: %w", b.Platform, operation, ErrUnsupported)
+}
+
+func (b *baseManager) ProfileName() string {
+	return b.Profile
+}
+
+func (b *baseManager) SetFilter(f Filter) {
+	b.Filter = f
+}
+
+func (b *baseManager) SetFinder(f FinderOptions) {
+	b.Finder = f
+}
+
+func (b *baseManager) SetConcurrency(n int) {
+	if b.Concurrency == 0 || n < b.Concurrency {
+		b.Concurrency = n
+	}
+}
+
+func (b *baseManager) SetCI() {
+	b.CI = true
+}
+
+func (b *baseManager) Create(args []string, secretsPath string, useEditor bool) error {
+	return createLocal(args, secretsPath, useEditor)
+}
+
+// fuzzyFind offers ids in the fuzzy finder and returns the selected ones
+func (b *baseManager) fuzzyFind(ids []string) ([]string, error) {
+	if err := helpers.RequireInteractive("selecting secrets", "pass the secret ids as arguments"); err != nil {
+		return nil, err
+	}
+	idxs, err := b.Finder.find(&ids)
+	if err != nil {
+		return nil, err
+	}
+	var selected []string
+	for _, idx := range idxs {
+		selected = append(selected, ids[idx])
+	}
+	return selected, nil
+}
+
+func (b *baseManager) Delete(int64) error {
+	return b.unsupported("delete")
+}
+
+func (b *baseManager) DeleteCancel([]string) error {
+	return b.unsupported("delete cancel")
+}
+
+func (b *baseManager) Describe(string) (SecretInfo, error) {
+	return SecretInfo{}, b.unsupported("describe")
+}
+
+func (b *baseManager) Erase(string) error {
+	return b.unsupported("erase")
+}
+
+func (b *baseManager) FuzzyFind(context.Context) ([]string, error) {
+	return nil, b.unsupported("fuzzy find")
+}
+
+func (b *baseManager) GetPolicy(string) (string, error) {
+	return "", b.unsupported("policy show")
+}
+
+func (b *baseManager) SetPolicy(string, string) error {
+	return b.unsupported("policy set")
+}
+
+func (b *baseManager) History(string) ([]SecretVersion, error) {
+	return nil, b.unsupported("history")
+}
+
+func (b *baseManager) Replicate([]string, []string) error {
+	return b.unsupported("replicate")
+}
+
+func (b *baseManager) Rollback() error {
+	return b.unsupported("rollback")
+}
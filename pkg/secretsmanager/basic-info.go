@@ -3,3 +3,29 @@ package secretsmanager
 func (a *AWSManager) ProfileName() string {
 	return a.Profile
 }
+
+func (a *AWSManager) SetFilter(f Filter) {
+	a.Filter = f
+}
+
+func (a *AWSManager) SetFinder(f FinderOptions) {
+	a.Finder = f
+}
+
+// SetConcurrency applies the general concurrency, a lower concurrency set on the manager is kept
+func (a *AWSManager) SetConcurrency(n int) {
+	if a.Concurrency == 0 || n < a.Concurrency {
+		a.Concurrency = n
+	}
+}
+
+// SetCI limits auth to environment variables and shortens retries, unless the manager sets its own
+func (a *AWSManager) SetCI() {
+	a.CI = true
+	if a.MaxAttempts == 0 {
+		a.MaxAttempts = 2
+	}
+	if a.MaxBackoff == "" {
+		a.MaxBackoff = "5s"
+	}
+}
@@ -3,33 +3,125 @@ package secretsmanager
 import (
 	"context"
 	"fmt"
+	"net"
+	"time"
 
+	"github.com/aws/aws-sdk-go-v2/aws"
+	"github.com/aws/aws-sdk-go-v2/aws/retry"
+	awshttp "github.com/aws/aws-sdk-go-v2/aws/transport/http"
 	"github.com/aws/aws-sdk-go-v2/config"
 	"github.com/aws/aws-sdk-go-v2/credentials"
+	"github.com/aws/aws-sdk-go-v2/credentials/stscreds"
 	"github.com/aws/aws-sdk-go-v2/service/secretsmanager"
+	"github.com/aws/aws-sdk-go-v2/service/sts"
+	jawsaws "github.com/jacbart/jaws/internal/aws"
 )
 
 // LoadAWSClient
 func LoadAWSClient(a *AWSManager, ctx context.Context) (*secretsmanager.Client, error) {
 	var client *secretsmanager.Client
+	var opts []func(*config.LoadOptions) error
 
-	if a.AccessID != "" {
-		cfg, err := config.LoadDefaultConfig(ctx,
-			config.WithCredentialsProvider(credentials.NewStaticCredentialsProvider(a.AccessID, a.SecretKey, "")),
-		)
-		if err != nil {
-			return nil, err
+	if a.CI {
+		// ci only trusts the environment, shared config and credentials files are never read
+		opts = append(opts, config.WithSharedConfigFiles([]string{}), config.WithSharedCredentialsFiles([]string{}))
+	} else if a.AccessID != "" {
+		opts = append(opts, config.WithCredentialsProvider(credentials.NewStaticCredentialsProvider(a.AccessID, a.SecretKey, "")))
+	} else if a.SourceProfile != "" {
+		opts = append(opts, config.WithSharedConfigProfile(a.SourceProfile))
+	}
+	if a.Region != "" {
+		opts = append(opts, config.WithRegion(a.Region))
+	}
+	// the adaptive retryer backs off exponentially with jitter and, while secrets manager throttles, slows
+	// down every request of the client, max_attempts bounds the requests of each call
+	var maxBackoff time.Duration
+	if a.MaxBackoff != "" {
+		var err error
+		if maxBackoff, err = time.ParseDuration(a.MaxBackoff); err != nil {
+			return nil, fmt.Errorf("max_backoff of %s: %w", a.Profile, err)
 		}
+	}
+	opts = append(opts, config.WithRetryer(func() aws.Retryer {
+		return retry.NewAdaptiveMode(func(o *retry.AdaptiveModeOptions) {
+			o.StandardOptions = append(o.StandardOptions, func(so *retry.StandardOptions) {
+				if a.MaxAttempts != 0 {
+					so.MaxAttempts = a.MaxAttempts
+				}
+				if maxBackoff != 0 {
+					so.MaxBackoff = maxBackoff
+				}
+			})
+		})
+	}))
 
-		client = secretsmanager.NewFromConfig(cfg)
-		return client, nil
+	// bound every request, so an unreachable endpoint fails instead of hanging
+	timeout, connectTimeout, err := a.timeouts()
+	if err != nil {
+		return nil, err
 	}
-	cfg, err := config.LoadDefaultConfig(ctx)
+	if timeout != 0 || connectTimeout != 0 {
+		httpClient := awshttp.NewBuildableClient()
+		if timeout != 0 {
+			httpClient = httpClient.WithTimeout(timeout)
+		}
+		if connectTimeout != 0 {
+			httpClient = httpClient.WithDialerOptions(func(d *net.Dialer) {
+				d.Timeout = connectTimeout
+			})
+		}
+		opts = append(opts, config.WithHTTPClient(httpClient))
+	}
+
+	cfg, err := config.LoadDefaultConfig(ctx, opts...)
 	if err != nil {
 		return nil, fmt.Errorf("unable to load AWS config, %v", err)
 	}
 
+	// chain into role_arn using the base credentials, so one source profile can reach many accounts
+	if a.RoleARN != "" {
+		provider := stscreds.NewAssumeRoleProvider(sts.NewFromConfig(cfg), a.RoleARN, func(o *stscreds.AssumeRoleOptions) {
+			o.RoleSessionName = fmt.Sprintf("jaws-%s", a.Profile)
+			if a.ExternalID != "" {
+				o.ExternalID = aws.String(a.ExternalID)
+			}
+		})
+		cfg.Credentials = aws.NewCredentialsCache(provider)
+	}
+
 	client = secretsmanager.NewFromConfig(cfg)
 
 	return client, nil
 }
+
+// timeouts parses the timeout and connect_timeout of the manager, unset ones are zero
+func (a *AWSManager) timeouts() (time.Duration, time.Duration, error) {
+	return parseTimeouts(a.Profile, a.Timeout, a.ConnectTimeout)
+}
+
+// parseTimeouts parses the timeout and connect_timeout of a manager profile, unset ones are zero
+func parseTimeouts(profile string, timeout string, connectTimeout string) (time.Duration, time.Duration, error) {
+	var t, connect time.Duration
+	var err error
+	if timeout != "" {
+		if t, err = time.ParseDuration(timeout); err != nil {
+			return 0, 0, fmt.Errorf("timeout of %s: %w", profile, err)
+		}
+	}
+	if connectTimeout != "" {
+		if connect, err = time.ParseDuration(connectTimeout); err != nil {
+			return 0, 0, fmt.Errorf("connect_timeout of %s: %w", profile, err)
+		}
+	}
+	return t, connect, nil
+}
+
+// context returns the context for the calls of one command, the timeout of the manager replaces
+// the default timeout of each call
+func (a *AWSManager) context() (context.Context, context.CancelFunc) {
+	ctx, cancel := context.WithCancel(context.Background())
+	if timeout, _, err := a.timeouts(); err == nil && timeout != 0 {
+		ctx = jawsaws.WithTimeout(ctx, timeout)
+	}
+	return ctx, cancel
+}
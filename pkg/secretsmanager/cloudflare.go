@@ -0,0 +1,242 @@
+package secretsmanager
+
+import (
+	"context"
+	"fmt"
+	"net/url"
+	"os"
+	"sort"
+
+	"github.com/fatih/color"
+	"github.com/jacbart/jaws/utils/helpers"
+)
+
+// CloudflareManager pushes secrets to a workers script or the environment variables of a pages project,
+// cloudflare never returns secret values so it is write-only
+type CloudflareManager struct {
+	baseManager
+	AccountID        string `hcl:"account_id"`
+	Worker           string `hcl:"worker,optional"`
+	PagesProject     string `hcl:"pages_project,optional"`
+	PagesEnvironment string `hcl:"pages_environment,optional"`
+	TokenEnv         string `hcl:"token_env,optional"`
+	Timeout          string `hcl:"timeout,optional"`
+	ConnectTimeout   string `hcl:"connect_timeout,optional"`
+}
+
+// NewCloudflareManager returns a cloudflare manager for profile
+func NewCloudflareManager(profile string) *CloudflareManager {
+	return &CloudflareManager{baseManager: baseManager{Platform: "cloudflare", Profile: profile}}
+}
+
+// client connects to the cloudflare api with the token in TokenEnv, or CLOUDFLARE_API_TOKEN
+func (cf *CloudflareManager) client() (*restClient, error) {
+	switch {
+	case cf.AccountID == "":
+		return nil, fmt.Errorf("cloudflare profile %s needs an account_id", cf.Profile)
+	case cf.Worker != "" && cf.PagesProject != "":
+		return nil, fmt.Errorf("cloudflare profile %s sets both worker and pages_project, pick one", cf.Profile)
+	case cf.Worker == "" && cf.PagesProject == "":
+		return nil, fmt.Errorf("cloudflare profile %s needs a worker or pages_project", cf.Profile)
+	}
+	tokenEnv := cf.TokenEnv
+	if tokenEnv == "" {
+		tokenEnv = "CLOUDFLARE_API_TOKEN"
+	}
+	token := os.Getenv(tokenEnv)
+	if token == "" {
+		return nil, &MissingTokenError{Env: tokenEnv, Platform: "cloudflare", Profile: cf.Profile}
+	}
+	timeout, connectTimeout, err := parseTimeouts(cf.Profile, cf.Timeout, cf.ConnectTimeout)
+	if err != nil {
+		return nil, err
+	}
+	return newRESTClient("https://api.cloudflare.com/client/v4", map[string]string{"Authorization": "Bearer " + token}, timeout, connectTimeout), nil
+}
+
+// target describes where the secrets go
+func (cf *CloudflareManager) target() string {
+	if cf.Worker != "" {
+		return fmt.Sprintf("cloudflare profile %s, worker %s", cf.Profile, cf.Worker)
+	}
+	return fmt.Sprintf("cloudflare profile %s, pages project %s (%s)", cf.Profile, cf.PagesProject, cf.environment())
+}
+
+func (cf *CloudflareManager) environment() string {
+	if cf.PagesEnvironment == "" {
+		return "production"
+	}
+	return cf.PagesEnvironment
+}
+
+func (cf *CloudflareManager) workerPath() string {
+	return fmt.Sprintf("/accounts/%s/workers/scripts/%s/secrets", cf.AccountID, url.PathEscape(cf.Worker))
+}
+
+func (cf *CloudflareManager) pagesPath() string {
+	return fmt.Sprintf("/accounts/%s/pages/projects/%s", cf.AccountID, url.PathEscape(cf.PagesProject))
+}
+
+// names lists the secrets of the worker or the variables of the pages environment
+func (cf *CloudflareManager) names(c *restClient) ([]string, error) {
+	var names []string
+	if cf.Worker != "" {
+		var resp struct {
+			Result []struct {
+				Name string `json:"name"`
+			} `json:"result"`
+		}
+		if err := c.do("GET", cf.workerPath(), nil, &resp); err != nil {
+			return nil, err
+		}
+		for _, s := range resp.Result {
+			names = append(names, s.Name)
+		}
+		return names, nil
+	}
+	var resp struct {
+		Result struct {
+			DeploymentConfigs map[string]struct {
+				EnvVars map[string]interface{} `json:"env_vars"`
+			} `json:"deployment_configs"`
+		} `json:"result"`
+	}
+	if err := c.do("GET", cf.pagesPath(), nil, &resp); err != nil {
+		return nil, err
+	}
+	for name, v := range resp.Result.DeploymentConfigs[cf.environment()].EnvVars {
+		// removed variables stay in the map as null
+		if v != nil {
+			names = append(names, name)
+		}
+	}
+	sort.Strings(names)
+	return names, nil
+}
+
+// write stores content as secret name, pages variables are patched into the project one at a time
+func (cf *CloudflareManager) write(c *restClient, name string, content string) error {
+	if cf.Worker != "" {
+		return c.do("PUT", cf.workerPath(), map[string]string{"name": name, "text": content, "type": "secret_text"}, nil)
+	}
+	return cf.patchPages(c, name, map[string]string{"type": "secret_text", "value": content})
+}
+
+// patchPages sets a variable of the pages environment, a nil value removes it
+func (cf *CloudflareManager) patchPages(c *restClient, name string, value interface{}) error {
+	body := map[string]interface{}{
+		"deployment_configs": map[string]interface{}{
+			cf.environment(): map[string]interface{}{
+				"env_vars": map[string]interface{}{name: value},
+			},
+		},
+	}
+	return c.do("PATCH", cf.pagesPath(), body, nil)
+}
+
+// CloudflareManager Get, cloudflare only returns the names of secrets
+func (cf *CloudflareManager) Get([]string) ([]Secret, error) {
+	return nil, cf.unsupported("get, workers and pages secrets are write-only")
+}
+
+// CloudflareManager ListDetails
+func (cf *CloudflareManager) ListDetails() ([]SecretInfo, error) {
+	c, err := cf.client()
+	if err != nil {
+		return nil, err
+	}
+	names, err := cf.names(c)
+	if err != nil {
+		return nil, err
+	}
+	var list []SecretInfo
+	for _, name := range names {
+		if cf.Filter.Match(name, nil) {
+			list = append(list, SecretInfo{ID: name})
+		}
+	}
+	return list, nil
+}
+
+// CloudflareManager ListAll
+func (cf *CloudflareManager) ListAll() ([]string, error) {
+	details, err := cf.ListDetails()
+	if err != nil {
+		return nil, err
+	}
+	var list []string
+	for _, s := range details {
+		list = append(list, s.ID)
+	}
+	return list, nil
+}
+
+func (cf *CloudflareManager) FuzzyFind(ctx context.Context) ([]string, error) {
+	ids, err := cf.ListAll()
+	if err != nil {
+		return nil, err
+	}
+	return cf.fuzzyFind(ids)
+}
+
+// CloudflareManager Put
+func (cf *CloudflareManager) Put(secretID string, content string) error {
+	if err := helpers.CheckValue(secretID, content); err != nil {
+		return err
+	}
+	c, err := cf.client()
+	if err != nil {
+		return err
+	}
+	return cf.write(c, secretID, content)
+}
+
+// CloudflareManager Set pushes every file in the secrets folder as the secret of the same name
+func (cf *CloudflareManager) Set(secretsPath string, opts SetOptions) ([]SetResult, error) {
+	c, err := cf.client()
+	if err != nil {
+		return nil, err
+	}
+	names, err := cf.names(c)
+	if err != nil {
+		return nil, err
+	}
+	existing := map[string]bool{}
+	for _, name := range names {
+		existing[name] = true
+	}
+	remote := func(id string) (RemoteValue, error) {
+		return RemoteValue{Exists: existing[id]}, nil
+	}
+	put := func(id string, content string) error {
+		return cf.write(c, id, content)
+	}
+	return pushFolder(secretsPath, cf.target(), opts, remote, put)
+}
+
+// CloudflareManager Erase
+func (cf *CloudflareManager) Erase(secretID string) error {
+	c, err := cf.client()
+	if err != nil {
+		return err
+	}
+	if cf.Worker != "" {
+		return c.do("DELETE", fmt.Sprintf("%s/%s", cf.workerPath(), url.PathEscape(secretID)), nil, nil)
+	}
+	return cf.patchPages(c, secretID, nil)
+}
+
+// CloudflareManager Delete removes the selected secrets, cloudflare keeps no deleted secrets to restore
+func (cf *CloudflareManager) Delete(int64) error {
+	ids, err := cf.FuzzyFind(context.Background())
+	if err != nil {
+		return err
+	}
+	for _, id := range ids {
+		if err = cf.Erase(id); err != nil {
+			return err
+		}
+		fmt.Printf("%s %s\n", id, color.RedString("deleted"))
+	}
+	return nil
+}
@@ -0,0 +1,43 @@
+package secretsmanager
+
+import (
+	"sync"
+)
+
+// DefaultConcurrency is the number of secrets fetched at once when no concurrency is configured
+const DefaultConcurrency = 10
+
+// limiter caps the number of requests in flight, throttling is left to the retryer of the client
+type limiter struct {
+	mu     sync.Mutex
+	cond   *sync.Cond
+	limit  int
+	active int
+}
+
+func newLimiter(limit int) *limiter {
+	if limit < 1 {
+		limit = DefaultConcurrency
+	}
+	l := &limiter{limit: limit}
+	l.cond = sync.NewCond(&l.mu)
+	return l
+}
+
+// acquire blocks until a request can start
+func (l *limiter) acquire() {
+	l.mu.Lock()
+	for l.active >= l.limit {
+		l.cond.Wait()
+	}
+	l.active++
+	l.mu.Unlock()
+}
+
+// release marks a request as done
+func (l *limiter) release() {
+	l.mu.Lock()
+	l.active--
+	l.cond.Broadcast()
+	l.mu.Unlock()
+}
@@ -0,0 +1,53 @@
+package secretsmanager
+
+import (
+	"sync"
+	"sync/atomic"
+	"testing"
+	"time"
+)
+
+func TestLimiter(t *testing.T) {
+	tests := []struct {
+		limit int
+		want  int
+	}{
+		{1, 1},
+		{3, 3},
+		{0, DefaultConcurrency},
+		{-2, DefaultConcurrency},
+	}
+	for _, tt := range tests {
+		lim := newLimiter(tt.limit)
+		if lim.limit != tt.want {
+			t.Errorf("newLimiter(%d).limit = %d, want %d", tt.limit, lim.limit, tt.want)
+		}
+		var active, peak, done int32
+		var wg sync.WaitGroup
+		for i := 0; i < 4*tt.want; i++ {
+			wg.Add(1)
+			go func() {
+				defer wg.Done()
+				lim.acquire()
+				defer lim.release()
+				n := atomic.AddInt32(&active, 1)
+				for {
+					p := atomic.LoadInt32(&peak)
+					if n <= p || atomic.CompareAndSwapInt32(&peak, p, n) {
+						break
+					}
+				}
+				time.Sleep(time.Millisecond)
+				atomic.AddInt32(&active, -1)
+				atomic.AddInt32(&done, 1)
+			}()
+		}
+		wg.Wait()
+		if peak > int32(tt.want) {
+			t.Errorf("limit %d: %d requests in flight at once, want at most %d", tt.limit, peak, tt.want)
+		}
+		if done != int32(4*tt.want) {
+			t.Errorf("limit %d: %d requests done, want %d", tt.limit, done, 4*tt.want)
+		}
+	}
+}
@@ -18,11 +18,23 @@ type Manager interface {
 	Create([]string, string, bool) error
 	Delete(int64) error
 	DeleteCancel([]string) error
+	Erase(string) error
+	Describe(string) (SecretInfo, error)
 	FuzzyFind(context.Context) ([]string, error)
 	Get([]string) ([]Secret, error)
+	GetPolicy(string) (string, error)
+	History(string) ([]SecretVersion, error)
 	ListAll() ([]string, error)
+	ListDetails() ([]SecretInfo, error)
+	Put(string, string) error
+	Replicate([]string, []string) error
 	Rollback() error
-	Set(string, bool) error
+	Set(string, SetOptions) ([]SetResult, error)
+	SetPolicy(string, string) error
+	SetFilter(Filter)
+	SetFinder(FinderOptions)
+	SetConcurrency(int)
+	SetCI()
 }
 
 type Config struct {
@@ -31,9 +43,33 @@ type Config struct {
 }
 
 type GeneralHCL struct {
-	DefaultProfile string `hcl:"default_profile,optional"`
-	Editor         string `hcl:"editor,optional"`
-	SecretsPath    string `hcl:"secrets_path,optional"`
+	DefaultProfile string       `hcl:"default_profile,optional"`
+	Editor         string       `hcl:"editor,optional"`
+	SecretsPath    string       `hcl:"secrets_path,optional"`
+	VCS            *bool        `hcl:"vcs,optional"`
+	VCSRemote      string       `hcl:"vcs_remote,optional"`
+	Validate       *ValidateHCL `hcl:"validate,block"`
+	Finder         *FinderHCL   `hcl:"finder,block"`
+	Concurrency    int          `hcl:"concurrency,optional"`
+	Policy         *PolicyHCL   `hcl:"policy,block"`
+}
+
+type PolicyHCL struct {
+	ProtectedProfiles  []string `hcl:"protected_profiles,optional"`
+	MinValueLength     int      `hcl:"min_value_length,optional"`
+	DenyValues         []string `hcl:"deny_values,optional"`
+	MaxRotationAgeDays int      `hcl:"max_rotation_age_days,optional"`
+}
+
+type ValidateHCL struct {
+	JSON               bool  `hcl:"json,optional"`
+	TrailingWhitespace bool  `hcl:"trailing_whitespace,optional"`
+	MaxSize            int64 `hcl:"max_size,optional"`
+}
+
+type FinderHCL struct {
+	Prompt      string `hcl:"prompt,optional"`
+	MultiSelect *bool  `hcl:"multi_select,optional"`
 }
 
 type managerHCL struct {
@@ -43,10 +79,23 @@ type managerHCL struct {
 }
 
 type AWSManager struct {
-	Profile   string
-	AccessID  string `hcl:"access_id,optional"`
-	SecretKey string `hcl:"secret_key,optional"`
-	Region    string `hcl:"region,optional"`
+	Profile        string
+	Filter         Filter
+	Finder         FinderOptions
+	CI             bool
+	AccessID       string   `hcl:"access_id,optional"`
+	SecretKey      string   `hcl:"secret_key,optional"`
+	SourceProfile  string   `hcl:"source_profile,optional"`
+	RoleARN        string   `hcl:"role_arn,optional"`
+	ExternalID     string   `hcl:"external_id,optional"`
+	Region         string   `hcl:"region,optional"`
+	KMSKeyID       string   `hcl:"kms_key_id,optional"`
+	ReplicaRegions []string `hcl:"replica_regions,optional"`
+	MaxAttempts    int      `hcl:"max_attempts,optional"`
+	MaxBackoff     string   `hcl:"max_backoff,optional"`
+	Timeout        string   `hcl:"timeout,optional"`
+	ConnectTimeout string   `hcl:"connect_timeout,optional"`
+	Concurrency    int      `hcl:"concurrency,optional"`
 }
 
 //go:embed config.tmpl
@@ -57,7 +106,7 @@ func CreateConfig() error {
 		General: GeneralHCL{
 			DefaultProfile: "default",
 			Editor:         os.Getenv("EDITOR"),
-			SecretsPath:    fmt.Sprintf("%s/.jaws/secrets", os.Getenv("HOME")),
+			SecretsPath:    helpers.ExpandHome("~/.jaws/secrets"),
 		},
 		Managers: []managerHCL{
 			{
@@ -11,14 +11,19 @@ import (
 
 // AWSManager Create
 func (a *AWSManager) Create(args []string, secretsPath string, useEditor bool) error {
+	return createLocal(args, secretsPath, useEditor)
+}
+
+// createLocal creates an empty secret file in the secrets folder, new secrets are only created remotely on set
+func createLocal(args []string, secretsPath string, useEditor bool) error {
 	pattern := strings.Split(args[0], "/")
 	filePath := fmt.Sprintf("%s/%s", secretsPath, args[0])
 	dir := fmt.Sprintf("%s/%s", secretsPath, strings.Join(pattern[:len(pattern)-1], "/"))
-	err := os.MkdirAll(dir, 0755)
+	err := os.MkdirAll(dir, helpers.SecretsDirMode)
 	if err != nil {
 		return err
 	}
-	f, err := os.Create(filePath)
+	f, err := os.OpenFile(filePath, os.O_RDWR|os.O_CREATE|os.O_TRUNC, helpers.SecretFileMode)
 	if err != nil {
 		return err
 	}
@@ -1,7 +1,6 @@
 package secretsmanager
 
 import (
-	"context"
 	"fmt"
 
 	"github.com/jacbart/jaws/internal/aws"
@@ -9,7 +8,7 @@ import (
 
 // AWSManager Delete
 func (a *AWSManager) Delete(scheduleInDays int64) error {
-	ctx, cancel := context.WithCancel(context.Background())
+	ctx, cancel := a.context()
 	defer cancel()
 
 	client, err := LoadAWSClient(a, ctx)
@@ -31,9 +30,21 @@ func (a *AWSManager) Delete(scheduleInDays int64) error {
 	return nil
 }
 
+// AWSManager Erase deletes a single secret without a recovery window
+func (a *AWSManager) Erase(secretID string) error {
+	ctx, cancel := a.context()
+	defer cancel()
+
+	client, err := LoadAWSClient(a, ctx)
+	if err != nil {
+		return err
+	}
+	return aws.ForceDelete(ctx, client, secretID)
+}
+
 // AWSManager DeleteCancel
 func (a *AWSManager) DeleteCancel(args []string) error {
-	ctx, cancel := context.WithCancel(context.Background())
+	ctx, cancel := a.context()
 	defer cancel()
 
 	client, err := LoadAWSClient(a, ctx)
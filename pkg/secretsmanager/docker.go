@@ -0,0 +1,114 @@
+package secretsmanager
+
+import (
+	"bufio"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"regexp"
+	"strings"
+)
+
+// DefaultDockerPrefix is the folder docker registry credentials are stored under
+const DefaultDockerPrefix = "docker-credentials/"
+
+// errCredentialsNotFound is the message docker expects when a helper has no credentials for a registry
+var errCredentialsNotFound = errors.New("credentials not found in native keychain")
+
+// invalidSecretChars matches the characters of a registry url that can not be part of a secret name
+var invalidSecretChars = regexp.MustCompile(`[^a-zA-Z0-9/_+=.@-]`)
+
+// dockerCredentials is the payload of the docker credential helper protocol and the content of the stored secret
+type dockerCredentials struct {
+	ServerURL string `json:"ServerURL"`
+	Username  string `json:"Username"`
+	Secret    string `json:"Secret"`
+}
+
+// dockerSecretID maps a registry url to the secret holding its credentials, the url itself is kept in the secret
+func dockerSecretID(prefix string, serverURL string) string {
+	id := strings.TrimPrefix(strings.TrimPrefix(serverURL, "https://"), "http://")
+	id = strings.TrimSuffix(id, "/")
+	return prefix + invalidSecretChars.ReplaceAllString(id, "-")
+}
+
+// DockerCredentialHelper runs one action of the docker credential helper protocol, get, store, erase or list,
+// reading the request from in and writing the response to out
+func DockerCredentialHelper(m Manager, action string, prefix string, in io.Reader, out io.Writer) error {
+	switch action {
+	case "get":
+		serverURL, err := readServerURL(in)
+		if err != nil {
+			return err
+		}
+		secrets, err := m.Get([]string{dockerSecretID(prefix, serverURL)})
+		if err != nil {
+			return err
+		}
+		if len(secrets) == 0 {
+			fmt.Fprintln(out, errCredentialsNotFound)
+			return ErrNotFound
+		}
+		var creds dockerCredentials
+		if err = json.Unmarshal([]byte(secrets[0].Content), &creds); err != nil {
+			return fmt.Errorf("decoding credentials of %s: %w", serverURL, err)
+		}
+		creds.ServerURL = serverURL
+		return json.NewEncoder(out).Encode(creds)
+	case "store":
+		var creds dockerCredentials
+		if err := json.NewDecoder(in).Decode(&creds); err != nil {
+			return fmt.Errorf("decoding credentials: %w", err)
+		}
+		if creds.ServerURL == "" {
+			return errors.New("no server url in credentials")
+		}
+		content, err := json.Marshal(creds)
+		if err != nil {
+			return err
+		}
+		return m.Put(dockerSecretID(prefix, creds.ServerURL), string(content))
+	case "erase":
+		serverURL, err := readServerURL(in)
+		if err != nil {
+			return err
+		}
+		return m.Erase(dockerSecretID(prefix, serverURL))
+	case "list":
+		m.SetFilter(Filter{Prefix: prefix})
+		ids, err := m.ListAll()
+		if err != nil {
+			return err
+		}
+		registries := map[string]string{}
+		if len(ids) != 0 {
+			secrets, err := m.Get(ids)
+			if err != nil {
+				return err
+			}
+			for _, s := range secrets {
+				var creds dockerCredentials
+				if err = json.Unmarshal([]byte(s.Content), &creds); err != nil || creds.ServerURL == "" {
+					continue
+				}
+				registries[creds.ServerURL] = creds.Username
+			}
+		}
+		return json.NewEncoder(out).Encode(registries)
+	}
+	return fmt.Errorf("unknown credential helper action %s, use get, store, erase or list", action)
+}
+
+// readServerURL reads the registry url docker writes to stdin for get and erase
+func readServerURL(in io.Reader) (string, error) {
+	line, err := bufio.NewReader(in).ReadString('\n')
+	if err != nil && err != io.EOF {
+		return "", err
+	}
+	serverURL := strings.TrimSpace(line)
+	if serverURL == "" {
+		return "", errors.New("no server url on stdin")
+	}
+	return serverURL, nil
+}
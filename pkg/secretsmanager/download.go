@@ -1,32 +1,21 @@
 package secretsmanager
 
 import (
+	"crypto/sha256"
 	"fmt"
 	"os"
-	"strings"
+
+	"github.com/jacbart/jaws/utils/helpers"
 )
 
-func DownloadSecret(secretID string, secretString string, secretsPath string) error {
-	pattern := strings.Split(secretID, "/")
+// DownloadSecret writes the secret to its file in the secrets folder, readable only by the current user,
+// a file that already holds the same content is left alone and false is returned
+func DownloadSecret(secretID string, secretString string, secretsPath string) (bool, error) {
 	filePath := fmt.Sprintf("%s/%s", secretsPath, secretID)
-	dir := fmt.Sprintf("%s/%s", secretsPath, strings.Join(pattern[:len(pattern)-1], "/"))
-	err := os.MkdirAll(dir, 0755)
-	if err != nil {
-		return err
-	}
-	f, err := os.Create(filePath)
-	if err != nil {
-		return err
-	}
-	defer f.Close()
-
-	_, err = f.WriteString(secretString)
-	if err != nil {
-		return err
-	}
-	err = f.Close()
-	if err != nil {
-		return err
+	if current, err := os.ReadFile(filePath); err == nil {
+		if sha256.Sum256(current) == sha256.Sum256([]byte(secretString)) {
+			return false, nil
+		}
 	}
-	return nil
+	return true, helpers.WriteSecretFile(filePath, []byte(secretString))
 }
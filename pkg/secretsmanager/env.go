@@ -0,0 +1,60 @@
+package secretsmanager
+
+import (
+	"encoding/json"
+	"fmt"
+	"sort"
+	"strings"
+)
+
+// EnvLines turns a secret into KEY="value" lines, a json object gives one line per field, a single
+// key is named after the last part of its path and anything else is read as dotenv lines and quoted the same way
+func EnvLines(s Secret) ([]string, error) {
+	if s.Binary {
+		return nil, fmt.Errorf("%s is binary, it can not be used as env vars", s.ID)
+	}
+	if s.Key != "" {
+		parts := strings.Split(s.Key, ".")
+		return []string{envLine(parts[len(parts)-1], s.Content)}, nil
+	}
+	var fields map[string]interface{}
+	if err := json.Unmarshal([]byte(s.Content), &fields); err != nil {
+		var lines []string
+		for i, line := range strings.Split(s.Content, "\n") {
+			if line = strings.TrimSpace(line); line == "" || strings.HasPrefix(line, "#") {
+				continue
+			}
+			name, value, ok := dotenvLine(line)
+			if !ok {
+				return nil, fmt.Errorf("line %d of %s is not KEY=value", i+1, s.ID)
+			}
+			lines = append(lines, envLine(name, value))
+		}
+		return lines, nil
+	}
+	keys := make([]string, 0, len(fields))
+	for k := range fields {
+		keys = append(keys, k)
+	}
+	sort.Strings(keys)
+	var lines []string
+	for _, k := range keys {
+		value, ok := fields[k].(string)
+		if !ok {
+			encoded, err := json.Marshal(fields[k])
+			if err != nil {
+				return nil, err
+			}
+			value = string(encoded)
+		}
+		lines = append(lines, envLine(k, value))
+	}
+	return lines, nil
+}
+
+// envLine quotes value so multi line values survive in an env file, $ is doubled so compose does not
+// interpolate it
+func envLine(key string, value string) string {
+	value = strings.NewReplacer(`\`, `\\`, `"`, `\"`, "\n", `\n`, "$", "$$").Replace(value)
+	return fmt.Sprintf(`%s="%s"`, key, value)
+}
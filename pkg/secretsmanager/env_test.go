@@ -0,0 +1,67 @@
+package secretsmanager
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestEnvLine(t *testing.T) {
+	tests := []struct {
+		key   string
+		value string
+		want  string
+	}{
+		{"API_KEY", "abc", `API_KEY="abc"`},
+		{"EMPTY", "", `EMPTY=""`},
+		{"QUOTED", `say "hi"`, `QUOTED="say \"hi\""`},
+		{"PATH", `C:\tools`, `PATH="C:\\tools"`},
+		{"PEM", "line1\nline2", `PEM="line1\nline2"`},
+		{"PRICE", "$5 and ${HOME}", `PRICE="$$5 and $${HOME}"`},
+	}
+	for _, tt := range tests {
+		if got := envLine(tt.key, tt.value); got != tt.want {
+			t.Errorf("envLine(%q, %q) = %s, want %s", tt.key, tt.value, got, tt.want)
+		}
+	}
+}
+
+func TestEnvLines(t *testing.T) {
+	tests := []struct {
+		secret  Secret
+		want    []string
+		wantErr bool
+	}{
+		{
+			secret: Secret{ID: "prod/app/config", Content: `{"b": "two", "a": "one", "port": 5432, "tls": {"on": true}}`},
+			want:   []string{`a="one"`, `b="two"`, `port="5432"`, `tls="{\"on\":true}"`},
+		},
+		{
+			secret: Secret{ID: "prod/app/config", Key: "database.password", Content: "hunter$22"},
+			want:   []string{`password="hunter$$22"`},
+		},
+		{
+			secret: Secret{ID: "prod/app/env", Content: "# comment\nexport API_KEY='abc'\n\nDEBUG=false\n"},
+			want:   []string{`API_KEY="abc"`, `DEBUG="false"`},
+		},
+		{
+			secret:  Secret{ID: "prod/app/env", Content: "API_KEY=abc\nnot a pair\n"},
+			wantErr: true,
+		},
+		{
+			secret:  Secret{ID: "prod/app/cert", Content: "\x00\x01", Binary: true},
+			wantErr: true,
+		},
+	}
+	for _, tt := range tests {
+		got, err := EnvLines(tt.secret)
+		if tt.wantErr {
+			if err == nil {
+				t.Errorf("EnvLines(%q) = %v, want an error", tt.secret.Content, got)
+			}
+			continue
+		}
+		if err != nil || !reflect.DeepEqual(got, tt.want) {
+			t.Errorf("EnvLines(%q) = %v, %v, want %v", tt.secret.Content, got, err, tt.want)
+		}
+	}
+}
@@ -1,6 +1,15 @@
 package secretsmanager
 
-import "fmt"
+import (
+	"errors"
+	"fmt"
+	"strings"
+
+	"github.com/aws/aws-sdk-go-v2/credentials/ssocreds"
+	"github.com/aws/aws-sdk-go-v2/service/secretsmanager/types"
+	"github.com/aws/smithy-go"
+	"github.com/jacbart/jaws/utils/helpers"
+)
 
 type NoConfigFileFound struct {
 	File  string
@@ -17,4 +26,230 @@ type DecodeConfigFailed struct {
 
 func (e *DecodeConfigFailed) Error() string {
 	return fmt.Sprintf("problem decoding %s", e.File)
-}
\ No newline at end of file
+}
+
+// MissingTokenError is returned when the env var holding the token of a manager is not set
+type MissingTokenError struct {
+	Env      string
+	Platform string
+	Profile  string
+}
+
+func (e *MissingTokenError) Error() string {
+	return fmt.Sprintf("%s is not set, %s profile %s needs a token", e.Env, e.Platform, e.Profile)
+}
+
+// ErrNotFound is returned when none of the requested secrets exist
+var ErrNotFound = errors.New("no secrets found")
+
+// ErrVCSDisabled is returned by commands that need the secrets folder history when vcs is turned off
+var ErrVCSDisabled = errors.New("vcs tracking is disabled, remove --no-vcs or set vcs = true in the config")
+
+// ErrUnsupported is returned when the platform of a manager has no equivalent of a command
+var ErrUnsupported = errors.New("not supported by this platform")
+
+// PartialFailure is returned when a command handled some secrets before failing
+type PartialFailure struct {
+	Done int
+	Err  error
+}
+
+func (e *PartialFailure) Error() string {
+	return fmt.Sprintf("%d secret(s) done before failing: %v", e.Done, e.Err)
+}
+
+func (e *PartialFailure) Unwrap() error {
+	return e.Err
+}
+
+// Exit codes returned by jaws so wrapper scripts can branch on the result
+const (
+	ExitOK       = 0
+	ExitFailure  = 1
+	ExitPartial  = 2
+	ExitAuth     = 3
+	ExitNotFound = 4
+)
+
+// ExitCode maps the error returned by a command to the exit code of jaws
+func ExitCode(err error) int {
+	if err == nil {
+		return ExitOK
+	}
+	var partial *PartialFailure
+	if errors.As(err, &partial) {
+		return ExitPartial
+	}
+	var rnfErr *types.ResourceNotFoundException
+	if errors.Is(err, ErrNotFound) || errors.As(err, &rnfErr) || isNotFound(err) {
+		return ExitNotFound
+	}
+	var tokenErr *MissingTokenError
+	if errors.As(err, &tokenErr) || isSSOExpired(err) {
+		return ExitAuth
+	}
+	var restErr *RESTError
+	if errors.As(err, &restErr) && (restErr.Status == 401 || restErr.Status == 403) {
+		return ExitAuth
+	}
+	var apiErr smithy.APIError
+	if errors.As(err, &apiErr) {
+		switch apiErr.ErrorCode() {
+		case "AccessDeniedException", "UnrecognizedClientException", "InvalidSignatureException",
+			"IncompleteSignature", "ExpiredTokenException", "InvalidClientTokenId":
+			return ExitAuth
+		}
+	}
+	return ExitFailure
+}
+
+// isSSOExpired reports whether err comes from an aws sso session that expired or was revoked
+func isSSOExpired(err error) bool {
+	var tokenErr *ssocreds.InvalidTokenError
+	if errors.As(err, &tokenErr) {
+		return true
+	}
+	var apiErr smithy.APIError
+	return errors.As(err, &apiErr) && apiErr.ErrorCode() == "UnauthorizedException"
+}
+
+// ErrorInfo explains an error code printed by jaws
+type ErrorInfo struct {
+	Code  string
+	Title string
+	Cause string
+	Fix   string
+}
+
+// errorCatalog lists every error code jaws prints, codes are stable and never reused
+var errorCatalog = []ErrorInfo{
+	{
+		Code:  "E001",
+		Title: "config not found",
+		Cause: "no jaws.conf was found in the current directory, ~/.jaws or ~/.config/jaws",
+		Fix:   "run `jaws config create > ~/.config/jaws/jaws.conf` or pass --config",
+	},
+	{
+		Code:  "E002",
+		Title: "config could not be decoded",
+		Cause: "the config file is not valid hcl or has unknown blocks or attributes",
+		Fix:   "compare it with `jaws config create` and fix the reported line",
+	},
+	{
+		Code:  "E003",
+		Title: "secret not found",
+		Cause: "the secret does not exist in the region and account of the profile, or is scheduled for deletion",
+		Fix:   "check the name with `jaws list --prefix`, the region in the manager block, or run `jaws delete cancel`",
+	},
+	{
+		Code:  "E004",
+		Title: "aws authentication failed",
+		Cause: "the credentials are missing, expired or not allowed to use secrets manager",
+		Fix:   "refresh the credentials of the profile, then check the iam policy of the role",
+	},
+	{
+		Code:  "E005",
+		Title: "partial failure",
+		Cause: "some secrets were handled before another one failed",
+		Fix:   "fix the reported error and run the command again, secrets that were already handled are skipped",
+	},
+	{
+		Code:  "E006",
+		Title: "vcs disabled",
+		Cause: "the command needs the git history of the secrets folder but vcs is turned off",
+		Fix:   "drop --no-vcs or set vcs = true in the general block of the config",
+	},
+	{
+		Code:  "E007",
+		Title: "prompt needed in non-interactive mode",
+		Cause: "jaws needed to ask a question but runs with --non-interactive or without a terminal on stdin",
+		Fix:   "pass the answer up front, e.g. secret ids as arguments or --no-prompt on set",
+	},
+	{
+		Code:  "E008",
+		Title: "validation failed",
+		Cause: "an edited secret failed one of the checks in the validate block of the config",
+		Fix:   "fix the reported secrets, or change the validate block, and run jaws set again",
+	},
+	{
+		Code:  "E009",
+		Title: "denied by policy",
+		Cause: "the command breaks a rule in the policy block of the config, a protected profile or a weak value",
+		Fix:   "add --confirm-prod for protected profiles, or use a value that passes min_value_length and deny_values",
+	},
+	{
+		Code:  "E010",
+		Title: "not supported by the platform",
+		Cause: "the platform of the profile has no equivalent of the command, e.g. a replica region outside of aws",
+		Fix:   "run the command with --profile set to a manager whose platform supports it",
+	},
+	{
+		Code:  "E011",
+		Title: "token not set",
+		Cause: "the env var holding the api token of a vault, github, gitlab, cloudflare, heroku or fly manager is empty",
+		Fix:   "export the env var named in the error, or point token_env in the manager block at one that is set",
+	},
+	{
+		Code:  "E012",
+		Title: "aws sso session expired",
+		Cause: "the sso session of the aws profile expired or was revoked",
+		Fix:   "run `aws sso login --profile <profile>` and run the command again",
+	},
+}
+
+// ErrorCode returns the catalog code of err, errors without a code return an empty string
+func ErrorCode(err error) string {
+	var noConfig *NoConfigFileFound
+	var decode *DecodeConfigFailed
+	var partial *PartialFailure
+	var prompt *helpers.PromptError
+	var validation *helpers.ValidationError
+	var policyErr *helpers.PolicyError
+	var tokenErr *MissingTokenError
+	switch {
+	case err == nil:
+		return ""
+	case errors.As(err, &partial):
+		return "E005"
+	case errors.As(err, &noConfig):
+		return "E001"
+	case errors.As(err, &decode):
+		return "E002"
+	case errors.Is(err, ErrVCSDisabled):
+		return "E006"
+	case errors.As(err, &prompt):
+		return "E007"
+	case errors.As(err, &validation):
+		return "E008"
+	case errors.As(err, &policyErr):
+		return "E009"
+	case errors.Is(err, ErrUnsupported):
+		return "E010"
+	case errors.As(err, &tokenErr):
+		return "E011"
+	case isSSOExpired(err):
+		return "E012"
+	}
+	switch ExitCode(err) {
+	case ExitNotFound:
+		return "E003"
+	case ExitAuth:
+		return "E004"
+	}
+	return ""
+}
+
+// Explain returns the catalog entry of code
+func Explain(code string) (ErrorInfo, bool) {
+	for _, info := range errorCatalog {
+		if strings.EqualFold(info.Code, code) {
+			return info, true
+		}
+	}
+	return ErrorInfo{}, false
+}
+
+// ErrorCatalog returns every error code jaws prints
+func ErrorCatalog() []ErrorInfo {
+	return errorCatalog
+}
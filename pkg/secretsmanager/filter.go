@@ -0,0 +1,22 @@
+package secretsmanager
+
+import "strings"
+
+// Filter narrows the secrets a manager lists and offers in the fuzzy finder
+type Filter struct {
+	Prefix string
+	Tags   map[string]string
+}
+
+// Match reports whether a secret name starts with the prefix and its tags contain every key and value of the filter
+func (f Filter) Match(name string, tags map[string]string) bool {
+	if !strings.HasPrefix(name, f.Prefix) {
+		return false
+	}
+	for k, v := range f.Tags {
+		if tv, ok := tags[k]; !ok || tv != v {
+			return false
+		}
+	}
+	return true
+}
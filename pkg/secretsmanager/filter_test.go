@@ -0,0 +1,28 @@
+package secretsmanager
+
+import "testing"
+
+func TestFilterMatch(t *testing.T) {
+	tags := map[string]string{"team": "payments", "env": "prod"}
+	tests := []struct {
+		filter Filter
+		name   string
+		tags   map[string]string
+		want   bool
+	}{
+		{Filter{}, "prod/app/config", nil, true},
+		{Filter{Prefix: "prod/"}, "prod/app/config", nil, true},
+		{Filter{Prefix: "dev/"}, "prod/app/config", nil, false},
+		{Filter{Tags: map[string]string{"team": "payments"}}, "prod/app/config", tags, true},
+		{Filter{Tags: map[string]string{"team": "payments", "env": "prod"}}, "prod/app/config", tags, true},
+		{Filter{Tags: map[string]string{"team": "search"}}, "prod/app/config", tags, false},
+		{Filter{Tags: map[string]string{"owner": "payments"}}, "prod/app/config", tags, false},
+		{Filter{Tags: map[string]string{"team": ""}}, "prod/app/config", nil, false},
+		{Filter{Prefix: "prod/", Tags: map[string]string{"env": "prod"}}, "dev/app/config", tags, false},
+	}
+	for _, tt := range tests {
+		if got := tt.filter.Match(tt.name, tt.tags); got != tt.want {
+			t.Errorf("%+v.Match(%q, %v) = %v, want %v", tt.filter, tt.name, tt.tags, got, tt.want)
+		}
+	}
+}
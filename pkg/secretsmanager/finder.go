@@ -0,0 +1,27 @@
+package secretsmanager
+
+import "github.com/ktr0731/go-fuzzyfinder"
+
+// FinderOptions change how the fuzzy finder used to select secrets behaves
+type FinderOptions struct {
+	Prompt       string
+	SingleSelect bool
+}
+
+// find opens the fuzzy finder over ids and returns the indexes of the selected ones
+func (f FinderOptions) find(ids *[]string, opts ...fuzzyfinder.Option) ([]int, error) {
+	if f.Prompt != "" {
+		opts = append(opts, fuzzyfinder.WithPromptString(f.Prompt))
+	}
+	itemFunc := func(i int) string {
+		return (*ids)[i]
+	}
+	if f.SingleSelect {
+		idx, err := fuzzyfinder.Find(ids, itemFunc, opts...)
+		if err != nil {
+			return nil, err
+		}
+		return []int{idx}, nil
+	}
+	return fuzzyfinder.FindMulti(ids, itemFunc, opts...)
+}
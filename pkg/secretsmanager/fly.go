@@ -0,0 +1,212 @@
+package secretsmanager
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+
+	"github.com/fatih/color"
+	"github.com/jacbart/jaws/utils/helpers"
+)
+
+// FlyManager pushes secrets to a fly.io app through the graphql api, fly only returns digests of secret
+// values so it is write-only
+type FlyManager struct {
+	baseManager
+	App            string `hcl:"app"`
+	TokenEnv       string `hcl:"token_env,optional"`
+	Timeout        string `hcl:"timeout,optional"`
+	ConnectTimeout string `hcl:"connect_timeout,optional"`
+}
+
+// NewFlyManager returns a fly manager for profile
+func NewFlyManager(profile string) *FlyManager {
+	return &FlyManager{baseManager: baseManager{Platform: "fly", Profile: profile}}
+}
+
+// client connects to the fly api with the token in TokenEnv, or FLY_API_TOKEN
+func (f *FlyManager) client() (*restClient, error) {
+	if f.App == "" {
+		return nil, fmt.Errorf("fly profile %s needs an app", f.Profile)
+	}
+	tokenEnv := f.TokenEnv
+	if tokenEnv == "" {
+		tokenEnv = "FLY_API_TOKEN"
+	}
+	token := os.Getenv(tokenEnv)
+	if token == "" {
+		return nil, &MissingTokenError{Env: tokenEnv, Platform: "fly", Profile: f.Profile}
+	}
+	timeout, connectTimeout, err := parseTimeouts(f.Profile, f.Timeout, f.ConnectTimeout)
+	if err != nil {
+		return nil, err
+	}
+	return newRESTClient("https://api.fly.io", map[string]string{"Authorization": "Bearer " + token}, timeout, connectTimeout), nil
+}
+
+// graphql runs query with variables and decodes the data of the response into out
+func (f *FlyManager) graphql(c *restClient, query string, variables map[string]interface{}, out interface{}) error {
+	var resp struct {
+		Data   json.RawMessage `json:"data"`
+		Errors []struct {
+			Message string `json:"message"`
+		} `json:"errors"`
+	}
+	if err := c.do("POST", "/graphql", map[string]interface{}{"query": query, "variables": variables}, &resp); err != nil {
+		return err
+	}
+	if len(resp.Errors) != 0 {
+		return errors.New("fly: " + resp.Errors[0].Message)
+	}
+	if out == nil {
+		return nil
+	}
+	return json.Unmarshal(resp.Data, out)
+}
+
+// names lists the secrets of the app
+func (f *FlyManager) names(c *restClient) ([]string, error) {
+	var data struct {
+		App struct {
+			Secrets []struct {
+				Name string `json:"name"`
+			} `json:"secrets"`
+		} `json:"app"`
+	}
+	query := `query($app: String!) { app(name: $app) { secrets { name } } }`
+	if err := f.graphql(c, query, map[string]interface{}{"app": f.App}, &data); err != nil {
+		return nil, err
+	}
+	var names []string
+	for _, s := range data.App.Secrets {
+		names = append(names, s.Name)
+	}
+	return names, nil
+}
+
+// write stores the secrets in one mutation, fly rolls out a new release of the app for each call
+func (f *FlyManager) write(c *restClient, secrets map[string]string) error {
+	query := `mutation($input: SetSecretsInput!) { setSecrets(input: $input) { release { id } } }`
+	var list []map[string]string
+	for name, content := range secrets {
+		list = append(list, map[string]string{"key": name, "value": content})
+	}
+	input := map[string]interface{}{
+		"appId":   f.App,
+		"secrets": list,
+	}
+	return f.graphql(c, query, map[string]interface{}{"input": input}, nil)
+}
+
+// FlyManager Get, fly only returns the names and digests of secrets
+func (f *FlyManager) Get([]string) ([]Secret, error) {
+	return nil, f.unsupported("get, app secrets are write-only")
+}
+
+// FlyManager ListDetails
+func (f *FlyManager) ListDetails() ([]SecretInfo, error) {
+	c, err := f.client()
+	if err != nil {
+		return nil, err
+	}
+	names, err := f.names(c)
+	if err != nil {
+		return nil, err
+	}
+	var list []SecretInfo
+	for _, name := range names {
+		if f.Filter.Match(name, nil) {
+			list = append(list, SecretInfo{ID: name})
+		}
+	}
+	return list, nil
+}
+
+// FlyManager ListAll
+func (f *FlyManager) ListAll() ([]string, error) {
+	details, err := f.ListDetails()
+	if err != nil {
+		return nil, err
+	}
+	var list []string
+	for _, s := range details {
+		list = append(list, s.ID)
+	}
+	return list, nil
+}
+
+func (f *FlyManager) FuzzyFind(ctx context.Context) ([]string, error) {
+	ids, err := f.ListAll()
+	if err != nil {
+		return nil, err
+	}
+	return f.fuzzyFind(ids)
+}
+
+// FlyManager Put
+func (f *FlyManager) Put(secretID string, content string) error {
+	if err := helpers.CheckValue(secretID, content); err != nil {
+		return err
+	}
+	c, err := f.client()
+	if err != nil {
+		return err
+	}
+	return f.write(c, map[string]string{secretID: content})
+}
+
+// FlyManager Set pushes every file in the secrets folder as the secret of the same name
+func (f *FlyManager) Set(secretsPath string, opts SetOptions) ([]SetResult, error) {
+	c, err := f.client()
+	if err != nil {
+		return nil, err
+	}
+	names, err := f.names(c)
+	if err != nil {
+		return nil, err
+	}
+	existing := map[string]bool{}
+	for _, name := range names {
+		existing[name] = true
+	}
+	remote := func(id string) (RemoteValue, error) {
+		return RemoteValue{Exists: existing[id]}, nil
+	}
+	put := func(changed map[string]string) error {
+		return f.write(c, changed)
+	}
+	return pushFolderBatch(secretsPath, fmt.Sprintf("fly profile %s, app %s", f.Profile, f.App), opts, remote, put)
+}
+
+// FlyManager Erase
+func (f *FlyManager) Erase(secretID string) error {
+	return f.erase([]string{secretID})
+}
+
+// erase removes the secrets in one mutation
+func (f *FlyManager) erase(ids []string) error {
+	c, err := f.client()
+	if err != nil {
+		return err
+	}
+	query := `mutation($input: UnsetSecretsInput!) { unsetSecrets(input: $input) { release { id } } }`
+	input := map[string]interface{}{"appId": f.App, "keys": ids}
+	return f.graphql(c, query, map[string]interface{}{"input": input}, nil)
+}
+
+// FlyManager Delete removes the selected secrets, fly keeps no deleted secrets to restore
+func (f *FlyManager) Delete(int64) error {
+	ids, err := f.FuzzyFind(context.Background())
+	if err != nil || len(ids) == 0 {
+		return err
+	}
+	if err = f.erase(ids); err != nil {
+		return err
+	}
+	for _, id := range ids {
+		fmt.Printf("%s %s\n", id, color.RedString("deleted"))
+	}
+	return nil
+}
@@ -4,21 +4,27 @@ import (
 	"context"
 	"errors"
 	"fmt"
+	"os"
+	"sync"
 
 	"github.com/aws/aws-sdk-go-v2/aws"
 	"github.com/aws/aws-sdk-go-v2/service/secretsmanager"
 	"github.com/aws/aws-sdk-go-v2/service/secretsmanager/types"
 	"github.com/fatih/color"
+	jawsaws "github.com/jacbart/jaws/internal/aws"
+	"github.com/jacbart/jaws/utils/helpers"
 )
 
 type Secret struct {
-	ID      string
-	Content string
+	ID      string `json:"id" yaml:"id"`
+	Key     string `json:"key,omitempty" yaml:"key,omitempty"`
+	Content string `json:"content" yaml:"content"`
+	Binary  bool   `json:"binary" yaml:"binary"`
 }
 
 // AWSManager Get
 func (a *AWSManager) Get(secretsIDList []string) ([]Secret, error) {
-	ctx, cancel := context.WithCancel(context.Background())
+	ctx, cancel := a.context()
 	defer cancel()
 	var Secrets []Secret
 
@@ -41,26 +47,83 @@ func (a *AWSManager) Get(secretsIDList []string) ([]Secret, error) {
 		secretIDs = secretsIDList
 	}
 
-	l := len(secretIDs)
-	var rnfErr *types.ResourceNotFoundException
+	// fetch the secrets in parallel, each result keeps the position of its id
+	results := make([]*Secret, len(secretIDs))
+	errs := make([]error, len(secretIDs))
+	lim := newLimiter(a.Concurrency)
+	var wg sync.WaitGroup
+	for i := range secretIDs {
+		wg.Add(1)
+		go func(i int) {
+			defer wg.Done()
+			lim.acquire()
+			defer lim.release()
+			results[i], errs[i] = a.getSecret(ctx, client, secretIDs[i])
+		}(i)
+	}
+	wg.Wait()
 
-	for i := 0; i < l; i++ {
-		vin := &secretsmanager.GetSecretValueInput{
-			SecretId: aws.String(secretIDs[i]),
+	for i := range secretIDs {
+		if errs[i] != nil {
+			return []Secret{}, errs[i]
 		}
-		vout, err := client.GetSecretValue(ctx, vin)
-		if err != nil {
-			if errors.As(err, &rnfErr) {
-				fmt.Printf("%s %s", color.RedString("no secret found called"), color.RedString(secretIDs[i]))
-			} else {
-				return []Secret{}, err
-			}
+		if results[i] != nil {
+			Secrets = append(Secrets, *results[i])
 		}
-		Secrets = append(Secrets, Secret{
-			ID:      secretIDs[i],
-			Content: *vout.SecretString,
-		})
 	}
 
 	return Secrets, nil
 }
+
+// getSecret fetches a single secret, a missing secret is reported and returns nil
+func (a *AWSManager) getSecret(ctx context.Context, client *secretsmanager.Client, secretID string) (*Secret, error) {
+	// name#database.password returns a single key of the value
+	ref, key := SplitKey(secretID)
+	// name@AWSPREVIOUS or name@<version-id> pins a version, the secret is still saved under its name
+	name, versionID, versionStage := jawsaws.SplitVersion(ref)
+	vin := &secretsmanager.GetSecretValueInput{
+		SecretId: aws.String(name),
+	}
+	if versionID != "" {
+		vin.VersionId = aws.String(versionID)
+	}
+	if versionStage != "" {
+		vin.VersionStage = aws.String(versionStage)
+	}
+	vout, err := client.GetSecretValue(ctx, vin)
+	if err != nil {
+		var rnfErr *types.ResourceNotFoundException
+		if errors.As(err, &rnfErr) {
+			fmt.Fprintf(os.Stderr, "%s %s\n", color.RedString("no secret found called"), color.RedString(name))
+			return nil, nil
+		}
+		return nil, err
+	}
+	// SecretBinary is already base64 decoded by the sdk, keep the raw bytes
+	if vout.SecretString == nil {
+		if key != "" {
+			return nil, fmt.Errorf("%s is binary, it has no key %s", name, key)
+		}
+		return &Secret{
+			ID:      name,
+			Content: string(vout.SecretBinary),
+			Binary:  true,
+		}, nil
+	}
+	helpers.RegisterSecret(*vout.SecretString)
+	if key != "" {
+		value, err := ExtractKey(*vout.SecretString, key)
+		if err != nil {
+			return nil, fmt.Errorf("%s: %w", name, err)
+		}
+		return &Secret{
+			ID:      name,
+			Key:     key,
+			Content: value,
+		}, nil
+	}
+	return &Secret{
+		ID:      name,
+		Content: *vout.SecretString,
+	}, nil
+}
@@ -0,0 +1,279 @@
+package secretsmanager
+
+import (
+	"context"
+	"crypto/rand"
+	"encoding/base64"
+	"fmt"
+	"net/url"
+	"os"
+	"regexp"
+	"strings"
+
+	"github.com/fatih/color"
+	"github.com/jacbart/jaws/utils/helpers"
+	"golang.org/x/crypto/nacl/box"
+)
+
+// GitHubManager pushes secrets to the Actions secrets of a repository, organization or repository environment,
+// github never returns secret values so it is write-only
+type GitHubManager struct {
+	baseManager
+	Repo           string `hcl:"repo,optional"`
+	Org            string `hcl:"org,optional"`
+	Environment    string `hcl:"environment,optional"`
+	Visibility     string `hcl:"visibility,optional"`
+	BaseURL        string `hcl:"base_url,optional"`
+	TokenEnv       string `hcl:"token_env,optional"`
+	Timeout        string `hcl:"timeout,optional"`
+	ConnectTimeout string `hcl:"connect_timeout,optional"`
+}
+
+// githubSecretName is what github accepts as the name of an actions secret
+var githubSecretName = regexp.MustCompile(`^[A-Za-z_][A-Za-z0-9_]*$`)
+
+// checkGitHubName fails for ids github would reject, like the ENV/APP/DEPLOYMENT/SecretType paths of other managers
+func checkGitHubName(id string) error {
+	if !githubSecretName.MatchString(id) || strings.HasPrefix(strings.ToUpper(id), "GITHUB_") {
+		return fmt.Errorf("%s is not a valid actions secret name, use letters, digits and _ without a GITHUB_ prefix", id)
+	}
+	return nil
+}
+
+// githubPublicKey is the key secrets of a scope are sealed with
+type githubPublicKey struct {
+	KeyID string `json:"key_id"`
+	Key   string `json:"key"`
+}
+
+// NewGitHubManager returns a github manager for profile
+func NewGitHubManager(profile string) *GitHubManager {
+	return &GitHubManager{baseManager: baseManager{Platform: "github", Profile: profile}}
+}
+
+// client connects to BaseURL, or api.github.com, with the token in TokenEnv, or GITHUB_TOKEN
+func (g *GitHubManager) client() (*restClient, error) {
+	base := g.BaseURL
+	if base == "" {
+		base = "https://api.github.com"
+	}
+	tokenEnv := g.TokenEnv
+	if tokenEnv == "" {
+		tokenEnv = "GITHUB_TOKEN"
+	}
+	token := os.Getenv(tokenEnv)
+	if token == "" {
+		return nil, &MissingTokenError{Env: tokenEnv, Platform: "github", Profile: g.Profile}
+	}
+	timeout, connectTimeout, err := parseTimeouts(g.Profile, g.Timeout, g.ConnectTimeout)
+	if err != nil {
+		return nil, err
+	}
+	return newRESTClient(base, map[string]string{
+		"Authorization":        "Bearer " + token,
+		"Accept":               "application/vnd.github+json",
+		"X-GitHub-Api-Version": "2022-11-28",
+	}, timeout, connectTimeout), nil
+}
+
+// scope is the api path of the secrets, the repo, org or environment set in the config
+func (g *GitHubManager) scope() (string, error) {
+	switch {
+	case g.Org != "" && g.Repo != "":
+		return "", fmt.Errorf("github profile %s sets both org and repo, pick one", g.Profile)
+	case g.Org != "":
+		return fmt.Sprintf("/orgs/%s/actions/secrets", g.Org), nil
+	case strings.Count(g.Repo, "/") != 1:
+		return "", fmt.Errorf("github profile %s needs repo = \"owner/repo\" or org", g.Profile)
+	case g.Environment != "":
+		return fmt.Sprintf("/repos/%s/environments/%s/secrets", g.Repo, g.Environment), nil
+	default:
+		return fmt.Sprintf("/repos/%s/actions/secrets", g.Repo), nil
+	}
+}
+
+// names lists the secrets in the scope, 100 per page
+func (g *GitHubManager) names(c *restClient, scope string) ([]string, error) {
+	var names []string
+	for page := 1; ; page++ {
+		var resp struct {
+			TotalCount int `json:"total_count"`
+			Secrets    []struct {
+				Name string `json:"name"`
+			} `json:"secrets"`
+		}
+		if err := c.do("GET", fmt.Sprintf("%s?per_page=100&page=%d", scope, page), nil, &resp); err != nil {
+			return nil, err
+		}
+		for _, s := range resp.Secrets {
+			names = append(names, s.Name)
+		}
+		if len(resp.Secrets) == 0 || len(names) >= resp.TotalCount {
+			return names, nil
+		}
+	}
+}
+
+// write seals content with the public key of the scope and stores it as secret name
+func (g *GitHubManager) write(c *restClient, scope string, key githubPublicKey, name string, content string) error {
+	raw, err := base64.StdEncoding.DecodeString(key.Key)
+	if err != nil || len(raw) != 32 {
+		return fmt.Errorf("github returned an invalid public key for %s", scope)
+	}
+	var recipient [32]byte
+	copy(recipient[:], raw)
+	sealed, err := box.SealAnonymous(nil, []byte(content), &recipient, rand.Reader)
+	if err != nil {
+		return err
+	}
+	body := map[string]string{
+		"encrypted_value": base64.StdEncoding.EncodeToString(sealed),
+		"key_id":          key.KeyID,
+	}
+	if g.Org != "" {
+		body["visibility"] = g.Visibility
+		if body["visibility"] == "" {
+			body["visibility"] = "private"
+		}
+	}
+	return c.do("PUT", fmt.Sprintf("%s/%s", scope, url.PathEscape(name)), body, nil)
+}
+
+// publicKey returns the key secrets of the scope are sealed with
+func (g *GitHubManager) publicKey(c *restClient, scope string) (githubPublicKey, error) {
+	var key githubPublicKey
+	err := c.do("GET", scope+"/public-key", nil, &key)
+	return key, err
+}
+
+// GitHubManager Get, github only returns the names of secrets
+func (g *GitHubManager) Get([]string) ([]Secret, error) {
+	return nil, g.unsupported("get, actions secrets are write-only")
+}
+
+// GitHubManager ListDetails
+func (g *GitHubManager) ListDetails() ([]SecretInfo, error) {
+	c, err := g.client()
+	if err != nil {
+		return nil, err
+	}
+	scope, err := g.scope()
+	if err != nil {
+		return nil, err
+	}
+	names, err := g.names(c, scope)
+	if err != nil {
+		return nil, err
+	}
+	var list []SecretInfo
+	for _, name := range names {
+		if g.Filter.Match(name, nil) {
+			list = append(list, SecretInfo{ID: name})
+		}
+	}
+	return list, nil
+}
+
+// GitHubManager ListAll
+func (g *GitHubManager) ListAll() ([]string, error) {
+	details, err := g.ListDetails()
+	if err != nil {
+		return nil, err
+	}
+	var list []string
+	for _, s := range details {
+		list = append(list, s.ID)
+	}
+	return list, nil
+}
+
+func (g *GitHubManager) FuzzyFind(ctx context.Context) ([]string, error) {
+	ids, err := g.ListAll()
+	if err != nil {
+		return nil, err
+	}
+	return g.fuzzyFind(ids)
+}
+
+// GitHubManager Put
+func (g *GitHubManager) Put(secretID string, content string) error {
+	if err := checkGitHubName(secretID); err != nil {
+		return err
+	}
+	if err := helpers.CheckValue(secretID, content); err != nil {
+		return err
+	}
+	c, err := g.client()
+	if err != nil {
+		return err
+	}
+	scope, err := g.scope()
+	if err != nil {
+		return err
+	}
+	key, err := g.publicKey(c, scope)
+	if err != nil {
+		return err
+	}
+	return g.write(c, scope, key, secretID, content)
+}
+
+// GitHubManager Set pushes every file in the secrets folder as the actions secret of the same name
+func (g *GitHubManager) Set(secretsPath string, opts SetOptions) ([]SetResult, error) {
+	c, err := g.client()
+	if err != nil {
+		return nil, err
+	}
+	scope, err := g.scope()
+	if err != nil {
+		return nil, err
+	}
+	names, err := g.names(c, scope)
+	if err != nil {
+		return nil, err
+	}
+	existing := map[string]bool{}
+	for _, name := range names {
+		existing[name] = true
+	}
+	key, err := g.publicKey(c, scope)
+	if err != nil {
+		return nil, err
+	}
+	// names are checked here so nothing is confirmed or pushed when one of them would be rejected
+	remote := func(id string) (RemoteValue, error) {
+		return RemoteValue{Exists: existing[id]}, checkGitHubName(id)
+	}
+	put := func(id string, content string) error {
+		return g.write(c, scope, key, id, content)
+	}
+	return pushFolder(secretsPath, fmt.Sprintf("github profile %s, %s", g.Profile, strings.TrimPrefix(scope, "/")), opts, remote, put)
+}
+
+// GitHubManager Erase
+func (g *GitHubManager) Erase(secretID string) error {
+	c, err := g.client()
+	if err != nil {
+		return err
+	}
+	scope, err := g.scope()
+	if err != nil {
+		return err
+	}
+	return c.do("DELETE", fmt.Sprintf("%s/%s", scope, url.PathEscape(secretID)), nil, nil)
+}
+
+// GitHubManager Delete removes the selected secrets, github keeps no deleted secrets to restore
+func (g *GitHubManager) Delete(int64) error {
+	ids, err := g.FuzzyFind(context.Background())
+	if err != nil {
+		return err
+	}
+	for _, id := range ids {
+		if err = g.Erase(id); err != nil {
+			return err
+		}
+		fmt.Printf("%s %s\n", id, color.RedString("deleted"))
+	}
+	return nil
+}
@@ -0,0 +1,313 @@
+package secretsmanager
+
+import (
+	"context"
+	"fmt"
+	"net/url"
+	"os"
+	"regexp"
+	"strconv"
+	"strings"
+
+	"github.com/fatih/color"
+	"github.com/jacbart/jaws/utils/helpers"
+)
+
+// GitLabManager keeps secrets in the ci/cd variables of a gitlab project or group
+type GitLabManager struct {
+	baseManager
+	Project          string `hcl:"project,optional"`
+	Group            string `hcl:"group,optional"`
+	BaseURL          string `hcl:"base_url,optional"`
+	TokenEnv         string `hcl:"token_env,optional"`
+	EnvironmentScope string `hcl:"environment_scope,optional"`
+	Masked           *bool  `hcl:"masked,optional"`
+	Protected        *bool  `hcl:"protected,optional"`
+	Timeout          string `hcl:"timeout,optional"`
+	ConnectTimeout   string `hcl:"connect_timeout,optional"`
+}
+
+// gitlabVariableKey is what gitlab accepts as the key of a ci/cd variable
+var gitlabVariableKey = regexp.MustCompile(`^[A-Za-z0-9_]{1,255}$`)
+
+// checkGitLabKey fails for ids gitlab would reject, like the ENV/APP/DEPLOYMENT/SecretType paths of other managers
+func checkGitLabKey(id string) error {
+	if !gitlabVariableKey.MatchString(id) {
+		return fmt.Errorf("%s is not a valid ci/cd variable key, use up to 255 letters, digits and _", id)
+	}
+	return nil
+}
+
+// gitlabVariable is a ci/cd variable as the gitlab api returns it
+type gitlabVariable struct {
+	Key              string `json:"key"`
+	Value            string `json:"value"`
+	VariableType     string `json:"variable_type"`
+	Protected        bool   `json:"protected"`
+	Masked           bool   `json:"masked"`
+	EnvironmentScope string `json:"environment_scope"`
+}
+
+// NewGitLabManager returns a gitlab manager for profile
+func NewGitLabManager(profile string) *GitLabManager {
+	return &GitLabManager{baseManager: baseManager{Platform: "gitlab", Profile: profile}}
+}
+
+// client connects to BaseURL, or gitlab.com, with the token in TokenEnv, or GITLAB_TOKEN
+func (g *GitLabManager) client() (*restClient, error) {
+	base := g.BaseURL
+	if base == "" {
+		base = "https://gitlab.com"
+	}
+	tokenEnv := g.TokenEnv
+	if tokenEnv == "" {
+		tokenEnv = "GITLAB_TOKEN"
+	}
+	token := os.Getenv(tokenEnv)
+	if token == "" {
+		return nil, &MissingTokenError{Env: tokenEnv, Platform: "gitlab", Profile: g.Profile}
+	}
+	timeout, connectTimeout, err := parseTimeouts(g.Profile, g.Timeout, g.ConnectTimeout)
+	if err != nil {
+		return nil, err
+	}
+	return newRESTClient(strings.TrimSuffix(base, "/")+"/api/v4", map[string]string{"PRIVATE-TOKEN": token}, timeout, connectTimeout), nil
+}
+
+// scope is the api path of the variables of the project or group set in the config
+func (g *GitLabManager) scope() (string, error) {
+	switch {
+	case g.Project != "" && g.Group != "":
+		return "", fmt.Errorf("gitlab profile %s sets both project and group, pick one", g.Profile)
+	case g.Project != "":
+		return fmt.Sprintf("/projects/%s/variables", url.PathEscape(g.Project)), nil
+	case g.Group != "":
+		return fmt.Sprintf("/groups/%s/variables", url.PathEscape(g.Group)), nil
+	default:
+		return "", fmt.Errorf("gitlab profile %s needs a project or group", g.Profile)
+	}
+}
+
+// variablePath is the api path of a single variable, project variables are narrowed to the environment scope
+func (g *GitLabManager) variablePath(scope string, key string) string {
+	path := fmt.Sprintf("%s/%s", scope, url.PathEscape(key))
+	if g.Project != "" && g.EnvironmentScope != "" {
+		path += "?filter[environment_scope]=" + url.QueryEscape(g.EnvironmentScope)
+	}
+	return path
+}
+
+// variables lists every variable in the scope, 100 per page
+func (g *GitLabManager) variables(c *restClient, scope string) ([]gitlabVariable, error) {
+	var all []gitlabVariable
+	for page := 1; ; page++ {
+		var vars []gitlabVariable
+		if err := c.do("GET", fmt.Sprintf("%s?per_page=100&page=%d", scope, page), nil, &vars); err != nil {
+			return nil, err
+		}
+		for _, v := range vars {
+			if g.EnvironmentScope == "" || v.EnvironmentScope == g.EnvironmentScope {
+				all = append(all, v)
+			}
+		}
+		if len(vars) < 100 {
+			return all, nil
+		}
+	}
+}
+
+// read returns a variable and whether it exists
+func (g *GitLabManager) read(c *restClient, scope string, key string) (gitlabVariable, bool, error) {
+	var v gitlabVariable
+	err := c.do("GET", g.variablePath(scope, key), nil, &v)
+	if isNotFound(err) {
+		return v, false, nil
+	}
+	return v, err == nil, err
+}
+
+// write updates a variable, or creates it, the masked and protected flags are only sent when the config
+// sets them so an update keeps the flags of the existing variable
+func (g *GitLabManager) write(c *restClient, scope string, key string, content string, exists bool) error {
+	body := map[string]interface{}{"value": content}
+	if g.Masked != nil {
+		body["masked"] = *g.Masked
+	}
+	if g.Protected != nil {
+		body["protected"] = *g.Protected
+	}
+	if g.EnvironmentScope != "" {
+		body["environment_scope"] = g.EnvironmentScope
+	}
+	if exists {
+		return c.do("PUT", g.variablePath(scope, key), body, nil)
+	}
+	body["key"] = key
+	return c.do("POST", scope, body, nil)
+}
+
+// GitLabManager Get
+func (g *GitLabManager) Get(secretsIDList []string) ([]Secret, error) {
+	c, err := g.client()
+	if err != nil {
+		return nil, err
+	}
+	scope, err := g.scope()
+	if err != nil {
+		return nil, err
+	}
+	secretIDs := secretsIDList
+	if len(secretIDs) == 0 {
+		if secretIDs, err = g.FuzzyFind(context.Background()); err != nil {
+			return nil, err
+		}
+	}
+	var secrets []Secret
+	for _, id := range secretIDs {
+		name, key := SplitKey(id)
+		v, exists, err := g.read(c, scope, name)
+		if err != nil {
+			return nil, err
+		}
+		if !exists {
+			fmt.Fprintf(os.Stderr, "%s %s\n", color.RedString("no secret found called"), color.RedString(name))
+			continue
+		}
+		helpers.RegisterSecret(v.Value)
+		content := v.Value
+		if key != "" {
+			if content, err = ExtractKey(content, key); err != nil {
+				return nil, fmt.Errorf("%s: %w", name, err)
+			}
+		}
+		secrets = append(secrets, Secret{ID: name, Key: key, Content: content})
+	}
+	return secrets, nil
+}
+
+// GitLabManager ListDetails, the masked, protected and environment settings are returned as tags
+func (g *GitLabManager) ListDetails() ([]SecretInfo, error) {
+	c, err := g.client()
+	if err != nil {
+		return nil, err
+	}
+	scope, err := g.scope()
+	if err != nil {
+		return nil, err
+	}
+	vars, err := g.variables(c, scope)
+	if err != nil {
+		return nil, err
+	}
+	var list []SecretInfo
+	for _, v := range vars {
+		tags := map[string]string{
+			"masked":    strconv.FormatBool(v.Masked),
+			"protected": strconv.FormatBool(v.Protected),
+		}
+		if v.EnvironmentScope != "" {
+			tags["environment_scope"] = v.EnvironmentScope
+		}
+		if g.Filter.Match(v.Key, tags) {
+			list = append(list, SecretInfo{ID: v.Key, Tags: tags})
+		}
+	}
+	return list, nil
+}
+
+// GitLabManager ListAll
+func (g *GitLabManager) ListAll() ([]string, error) {
+	details, err := g.ListDetails()
+	if err != nil {
+		return nil, err
+	}
+	var list []string
+	for _, s := range details {
+		list = append(list, s.ID)
+	}
+	return list, nil
+}
+
+func (g *GitLabManager) FuzzyFind(ctx context.Context) ([]string, error) {
+	ids, err := g.ListAll()
+	if err != nil {
+		return nil, err
+	}
+	return g.fuzzyFind(ids)
+}
+
+// GitLabManager Put
+func (g *GitLabManager) Put(secretID string, content string) error {
+	if err := checkGitLabKey(secretID); err != nil {
+		return err
+	}
+	if err := helpers.CheckValue(secretID, content); err != nil {
+		return err
+	}
+	c, err := g.client()
+	if err != nil {
+		return err
+	}
+	scope, err := g.scope()
+	if err != nil {
+		return err
+	}
+	_, exists, err := g.read(c, scope, secretID)
+	if err != nil {
+		return err
+	}
+	return g.write(c, scope, secretID, content, exists)
+}
+
+// GitLabManager Set pushes every file in the secrets folder as the variable of the same name
+func (g *GitLabManager) Set(secretsPath string, opts SetOptions) ([]SetResult, error) {
+	c, err := g.client()
+	if err != nil {
+		return nil, err
+	}
+	scope, err := g.scope()
+	if err != nil {
+		return nil, err
+	}
+	existing := map[string]bool{}
+	remote := func(id string) (RemoteValue, error) {
+		if err := checkGitLabKey(id); err != nil {
+			return RemoteValue{}, err
+		}
+		v, exists, err := g.read(c, scope, id)
+		existing[id] = exists
+		return RemoteValue{Content: []byte(v.Value), Exists: exists}, err
+	}
+	put := func(id string, content string) error {
+		return g.write(c, scope, id, content, existing[id])
+	}
+	return pushFolder(secretsPath, fmt.Sprintf("gitlab profile %s, %s", g.Profile, strings.TrimPrefix(scope, "/")), opts, remote, put)
+}
+
+// GitLabManager Erase
+func (g *GitLabManager) Erase(secretID string) error {
+	c, err := g.client()
+	if err != nil {
+		return err
+	}
+	scope, err := g.scope()
+	if err != nil {
+		return err
+	}
+	return c.do("DELETE", g.variablePath(scope, secretID), nil, nil)
+}
+
+// GitLabManager Delete removes the selected variables, gitlab keeps no deleted variables to restore
+func (g *GitLabManager) Delete(int64) error {
+	ids, err := g.FuzzyFind(context.Background())
+	if err != nil {
+		return err
+	}
+	for _, id := range ids {
+		if err = g.Erase(id); err != nil {
+			return err
+		}
+		fmt.Printf("%s %s\n", id, color.RedString("deleted"))
+	}
+	return nil
+}
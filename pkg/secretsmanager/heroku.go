@@ -0,0 +1,229 @@
+package secretsmanager
+
+import (
+	"context"
+	"fmt"
+	"net/url"
+	"os"
+	"regexp"
+	"sort"
+
+	"github.com/fatih/color"
+	"github.com/jacbart/jaws/utils/helpers"
+)
+
+// HerokuManager keeps secrets in the config vars of a heroku app
+type HerokuManager struct {
+	baseManager
+	App            string `hcl:"app"`
+	TokenEnv       string `hcl:"token_env,optional"`
+	Timeout        string `hcl:"timeout,optional"`
+	ConnectTimeout string `hcl:"connect_timeout,optional"`
+}
+
+// herokuConfigVarName is what heroku accepts as the name of a config var
+var herokuConfigVarName = regexp.MustCompile(`^[A-Za-z_][A-Za-z0-9_]*$`)
+
+// checkHerokuName fails for ids heroku would reject, like the ENV/APP/DEPLOYMENT/SecretType paths of other managers
+func checkHerokuName(id string) error {
+	if !herokuConfigVarName.MatchString(id) {
+		return fmt.Errorf("%s is not a valid config var name, use letters, digits and _ not starting with a digit", id)
+	}
+	return nil
+}
+
+// NewHerokuManager returns a heroku manager for profile
+func NewHerokuManager(profile string) *HerokuManager {
+	return &HerokuManager{baseManager: baseManager{Platform: "heroku", Profile: profile}}
+}
+
+// client connects to the heroku platform api with the token in TokenEnv, or HEROKU_API_KEY
+func (h *HerokuManager) client() (*restClient, error) {
+	if h.App == "" {
+		return nil, fmt.Errorf("heroku profile %s needs an app", h.Profile)
+	}
+	tokenEnv := h.TokenEnv
+	if tokenEnv == "" {
+		tokenEnv = "HEROKU_API_KEY"
+	}
+	token := os.Getenv(tokenEnv)
+	if token == "" {
+		return nil, &MissingTokenError{Env: tokenEnv, Platform: "heroku", Profile: h.Profile}
+	}
+	timeout, connectTimeout, err := parseTimeouts(h.Profile, h.Timeout, h.ConnectTimeout)
+	if err != nil {
+		return nil, err
+	}
+	return newRESTClient("https://api.heroku.com", map[string]string{
+		"Authorization": "Bearer " + token,
+		"Accept":        "application/vnd.heroku+json; version=3",
+	}, timeout, connectTimeout), nil
+}
+
+func (h *HerokuManager) configPath() string {
+	return fmt.Sprintf("/apps/%s/config-vars", url.PathEscape(h.App))
+}
+
+// configVars returns every config var of the app
+func (h *HerokuManager) configVars(c *restClient) (map[string]string, error) {
+	vars := map[string]string{}
+	err := c.do("GET", h.configPath(), nil, &vars)
+	return vars, err
+}
+
+// patch sets config vars of the app, a nil value removes the var
+func (h *HerokuManager) patch(c *restClient, vars map[string]*string) error {
+	return c.do("PATCH", h.configPath(), vars, nil)
+}
+
+// HerokuManager Get
+func (h *HerokuManager) Get(secretsIDList []string) ([]Secret, error) {
+	c, err := h.client()
+	if err != nil {
+		return nil, err
+	}
+	secretIDs := secretsIDList
+	if len(secretIDs) == 0 {
+		if secretIDs, err = h.FuzzyFind(context.Background()); err != nil {
+			return nil, err
+		}
+	}
+	vars, err := h.configVars(c)
+	if err != nil {
+		return nil, err
+	}
+	var secrets []Secret
+	for _, id := range secretIDs {
+		name, key := SplitKey(id)
+		content, ok := vars[name]
+		if !ok {
+			fmt.Fprintf(os.Stderr, "%s %s\n", color.RedString("no secret found called"), color.RedString(name))
+			continue
+		}
+		helpers.RegisterSecret(content)
+		if key != "" {
+			if content, err = ExtractKey(content, key); err != nil {
+				return nil, fmt.Errorf("%s: %w", name, err)
+			}
+		}
+		secrets = append(secrets, Secret{ID: name, Key: key, Content: content})
+	}
+	return secrets, nil
+}
+
+// HerokuManager ListDetails
+func (h *HerokuManager) ListDetails() ([]SecretInfo, error) {
+	c, err := h.client()
+	if err != nil {
+		return nil, err
+	}
+	vars, err := h.configVars(c)
+	if err != nil {
+		return nil, err
+	}
+	var list []SecretInfo
+	for name := range vars {
+		if h.Filter.Match(name, nil) {
+			list = append(list, SecretInfo{ID: name})
+		}
+	}
+	sort.Slice(list, func(i, j int) bool {
+		return list[i].ID < list[j].ID
+	})
+	return list, nil
+}
+
+// HerokuManager ListAll
+func (h *HerokuManager) ListAll() ([]string, error) {
+	details, err := h.ListDetails()
+	if err != nil {
+		return nil, err
+	}
+	var list []string
+	for _, s := range details {
+		list = append(list, s.ID)
+	}
+	return list, nil
+}
+
+func (h *HerokuManager) FuzzyFind(ctx context.Context) ([]string, error) {
+	ids, err := h.ListAll()
+	if err != nil {
+		return nil, err
+	}
+	return h.fuzzyFind(ids)
+}
+
+// HerokuManager Put
+func (h *HerokuManager) Put(secretID string, content string) error {
+	if err := checkHerokuName(secretID); err != nil {
+		return err
+	}
+	if err := helpers.CheckValue(secretID, content); err != nil {
+		return err
+	}
+	c, err := h.client()
+	if err != nil {
+		return err
+	}
+	return h.patch(c, map[string]*string{secretID: &content})
+}
+
+// HerokuManager Set pushes every file in the secrets folder as the config var of the same name
+func (h *HerokuManager) Set(secretsPath string, opts SetOptions) ([]SetResult, error) {
+	c, err := h.client()
+	if err != nil {
+		return nil, err
+	}
+	vars, err := h.configVars(c)
+	if err != nil {
+		return nil, err
+	}
+	remote := func(id string) (RemoteValue, error) {
+		content, exists := vars[id]
+		return RemoteValue{Content: []byte(content), Exists: exists}, checkHerokuName(id)
+	}
+	// one patch, heroku restarts the app for every change of its config vars
+	put := func(changed map[string]string) error {
+		patch := map[string]*string{}
+		for id := range changed {
+			content := changed[id]
+			patch[id] = &content
+		}
+		return h.patch(c, patch)
+	}
+	return pushFolderBatch(secretsPath, fmt.Sprintf("heroku profile %s, app %s", h.Profile, h.App), opts, remote, put)
+}
+
+// HerokuManager Erase
+func (h *HerokuManager) Erase(secretID string) error {
+	return h.erase([]string{secretID})
+}
+
+// erase removes the config vars in one patch
+func (h *HerokuManager) erase(ids []string) error {
+	c, err := h.client()
+	if err != nil {
+		return err
+	}
+	patch := map[string]*string{}
+	for _, id := range ids {
+		patch[id] = nil
+	}
+	return h.patch(c, patch)
+}
+
+// HerokuManager Delete removes the selected config vars, heroku keeps no deleted vars to restore
+func (h *HerokuManager) Delete(int64) error {
+	ids, err := h.FuzzyFind(context.Background())
+	if err != nil || len(ids) == 0 {
+		return err
+	}
+	if err = h.erase(ids); err != nil {
+		return err
+	}
+	for _, id := range ids {
+		fmt.Printf("%s %s\n", id, color.RedString("deleted"))
+	}
+	return nil
+}
@@ -0,0 +1,46 @@
+package secretsmanager
+
+import (
+	"sort"
+	"time"
+
+	"github.com/jacbart/jaws/internal/aws"
+)
+
+// SecretVersion is a stored version of a secret and the staging labels attached to it
+type SecretVersion struct {
+	ID      string    `json:"id" yaml:"id"`
+	Stages  []string  `json:"stages" yaml:"stages"`
+	Created time.Time `json:"created" yaml:"created"`
+}
+
+// AWSManager History
+func (a *AWSManager) History(secretID string) ([]SecretVersion, error) {
+	ctx, cancel := a.context()
+	defer cancel()
+
+	client, err := LoadAWSClient(a, ctx)
+	if err != nil {
+		return []SecretVersion{}, err
+	}
+
+	entries, err := aws.ListVersions(ctx, client, secretID)
+	if err != nil {
+		return []SecretVersion{}, err
+	}
+	var versions []SecretVersion
+	for _, e := range entries {
+		v := SecretVersion{
+			ID:     *e.VersionId,
+			Stages: e.VersionStages,
+		}
+		if e.CreatedDate != nil {
+			v.Created = *e.CreatedDate
+		}
+		versions = append(versions, v)
+	}
+	sort.Slice(versions, func(i, j int) bool {
+		return versions[i].Created.After(versions[j].Created)
+	})
+	return versions, nil
+}
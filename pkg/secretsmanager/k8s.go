@@ -0,0 +1,191 @@
+package secretsmanager
+
+import (
+	"bytes"
+	"encoding/base64"
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"os/exec"
+	"sort"
+	"time"
+
+	"github.com/fatih/color"
+	"gopkg.in/yaml.v3"
+)
+
+// K8sMapping lists the Kubernetes Secrets rendered by jaws k8s-sync
+type K8sMapping struct {
+	Secrets []K8sSecretMapping `yaml:"secrets"`
+}
+
+// K8sSecretMapping renders the secret From into the Kubernetes Secret Name, a json object is split into one
+// key per field unless Key is set, any other value is stored under Key or `value`
+type K8sSecretMapping struct {
+	Name string `yaml:"name"`
+	From string `yaml:"from"`
+	Key  string `yaml:"key,omitempty"`
+	Type string `yaml:"type,omitempty"`
+}
+
+// K8sSyncOptions are the settings of jaws k8s-sync, a zero Watch renders once
+type K8sSyncOptions struct {
+	MapFile   string
+	Namespace string
+	Apply     bool
+	Watch     time.Duration
+}
+
+// k8sSecret is the manifest of a Kubernetes Secret
+type k8sSecret struct {
+	APIVersion string            `yaml:"apiVersion"`
+	Kind       string            `yaml:"kind"`
+	Metadata   k8sMetadata       `yaml:"metadata"`
+	Type       string            `yaml:"type"`
+	Data       map[string]string `yaml:"data"`
+}
+
+type k8sMetadata struct {
+	Name      string            `yaml:"name"`
+	Namespace string            `yaml:"namespace,omitempty"`
+	Labels    map[string]string `yaml:"labels"`
+}
+
+// LoadK8sMapping reads and checks a k8s-sync mapping file
+func LoadK8sMapping(path string) (K8sMapping, error) {
+	var mapping K8sMapping
+	content, err := os.ReadFile(path)
+	if err != nil {
+		return mapping, err
+	}
+	if err = yaml.Unmarshal(content, &mapping); err != nil {
+		return mapping, fmt.Errorf("decoding %s: %w", path, err)
+	}
+	if len(mapping.Secrets) == 0 {
+		return mapping, fmt.Errorf("%s maps no secrets", path)
+	}
+	for i, s := range mapping.Secrets {
+		if s.Name == "" || s.From == "" {
+			return mapping, fmt.Errorf("secret %d in %s needs a name and from", i+1, path)
+		}
+	}
+	return mapping, nil
+}
+
+// RenderK8sSecrets fetches the mapped secrets and renders them as Kubernetes Secret manifests
+func RenderK8sSecrets(m Manager, mapping K8sMapping, namespace string) ([]byte, error) {
+	var ids []string
+	for _, s := range mapping.Secrets {
+		ids = append(ids, s.From)
+	}
+	secrets, err := m.Get(ids)
+	if err != nil {
+		return nil, err
+	}
+
+	var out bytes.Buffer
+	enc := yaml.NewEncoder(&out)
+	enc.SetIndent(2)
+	// Get keeps the order of the refs and skips missing secrets, so the results are paired by position
+	j := 0
+	for _, s := range mapping.Secrets {
+		if j >= len(secrets) || RefSecretID(s.From) != secrets[j].ID {
+			return nil, fmt.Errorf("%s for %s: %w", s.From, s.Name, ErrNotFound)
+		}
+		content := secrets[j].Content
+		j++
+		manifest := k8sSecret{
+			APIVersion: "v1",
+			Kind:       "Secret",
+			Metadata: k8sMetadata{
+				Name:      s.Name,
+				Namespace: namespace,
+				Labels:    map[string]string{"app.kubernetes.io/managed-by": "jaws"},
+			},
+			Type: s.Type,
+			Data: k8sData(s, content),
+		}
+		if manifest.Type == "" {
+			manifest.Type = "Opaque"
+		}
+		if err = enc.Encode(manifest); err != nil {
+			return nil, err
+		}
+	}
+	if err = enc.Close(); err != nil {
+		return nil, err
+	}
+	return out.Bytes(), nil
+}
+
+// k8sData base64 encodes the value of a secret into the data keys of its manifest
+func k8sData(s K8sSecretMapping, content string) map[string]string {
+	data := map[string]string{}
+	if s.Key == "" {
+		var fields map[string]interface{}
+		if json.Unmarshal([]byte(content), &fields) == nil {
+			keys := make([]string, 0, len(fields))
+			for k := range fields {
+				keys = append(keys, k)
+			}
+			sort.Strings(keys)
+			for _, k := range keys {
+				value, ok := fields[k].(string)
+				if !ok {
+					encoded, _ := json.Marshal(fields[k])
+					value = string(encoded)
+				}
+				data[k] = base64.StdEncoding.EncodeToString([]byte(value))
+			}
+			return data
+		}
+		s.Key = "value"
+	}
+	data[s.Key] = base64.StdEncoding.EncodeToString([]byte(content))
+	return data
+}
+
+// K8sSync renders the mapped secrets and prints them or applies them with kubectl, with Watch set it renders
+// again every interval and only prints or applies when a secret changed
+func K8sSync(m Manager, opts K8sSyncOptions, out io.Writer) error {
+	mapping, err := LoadK8sMapping(opts.MapFile)
+	if err != nil {
+		return err
+	}
+	var last []byte
+	for {
+		manifests, err := RenderK8sSecrets(m, mapping, opts.Namespace)
+		if err != nil {
+			return err
+		}
+		if !bytes.Equal(manifests, last) {
+			if opts.Apply {
+				if err = kubectlApply(manifests); err != nil {
+					return err
+				}
+			} else {
+				fmt.Fprint(out, "---\n")
+				out.Write(manifests)
+			}
+			last = manifests
+		}
+		if opts.Watch == 0 {
+			return nil
+		}
+		time.Sleep(opts.Watch)
+	}
+}
+
+// kubectlApply pipes manifests to kubectl apply
+func kubectlApply(manifests []byte) error {
+	c := exec.Command("kubectl", "apply", "-f", "-")
+	c.Stdin = bytes.NewReader(manifests)
+	c.Stdout = os.Stdout
+	c.Stderr = os.Stderr
+	if err := c.Run(); err != nil {
+		return fmt.Errorf("kubectl apply: %w", err)
+	}
+	fmt.Fprintf(os.Stderr, "%s %s\n", color.GreenString("applied"), time.Now().Format("2006-01-02 15:04:05"))
+	return nil
+}
@@ -0,0 +1,188 @@
+package secretsmanager
+
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"strconv"
+	"strings"
+
+	"github.com/fatih/color"
+	jawsaws "github.com/jacbart/jaws/internal/aws"
+	"github.com/jacbart/jaws/utils/helpers"
+)
+
+// SplitKey splits name#database.password into the secret ref and the key path inside its value,
+// # can not be part of a secret name
+func SplitKey(ref string) (string, string) {
+	i := strings.LastIndex(ref, "#")
+	if i == -1 {
+		return ref, ""
+	}
+	return ref[:i], ref[i+1:]
+}
+
+// RefSecretID returns the name of the secret a ref like name@AWSPREVIOUS#key points to
+func RefSecretID(ref string) string {
+	ref, _ = SplitKey(ref)
+	name, _, _ := jawsaws.SplitVersion(ref)
+	return name
+}
+
+// ExtractKey returns the value at the dotted key path in a json secret, array elements are addressed by index,
+// values that are not json are read as dotenv KEY=value lines
+func ExtractKey(content string, path string) (string, error) {
+	var doc interface{}
+	if err := json.Unmarshal([]byte(content), &doc); err != nil {
+		if value, ok := dotenvValue(content, path); ok {
+			return value, nil
+		}
+		return "", fmt.Errorf("key %s: %w", path, ErrNotFound)
+	}
+	value := doc
+	for _, segment := range strings.Split(path, ".") {
+		switch v := value.(type) {
+		case map[string]interface{}:
+			next, ok := v[segment]
+			if !ok {
+				return "", fmt.Errorf("key %s: %w", path, ErrNotFound)
+			}
+			value = next
+		case []interface{}:
+			i, err := strconv.Atoi(segment)
+			if err != nil || i < 0 || i >= len(v) {
+				return "", fmt.Errorf("key %s: %w", path, ErrNotFound)
+			}
+			value = v[i]
+		default:
+			return "", fmt.Errorf("key %s: %w", path, ErrNotFound)
+		}
+	}
+	if s, ok := value.(string); ok {
+		return s, nil
+	}
+	encoded, err := json.Marshal(value)
+	return string(encoded), err
+}
+
+// dotenvValue finds key in KEY=value lines, export prefixes and surrounding quotes are dropped
+func dotenvValue(content string, key string) (string, bool) {
+	for _, line := range strings.Split(content, "\n") {
+		if name, value, ok := dotenvLine(line); ok && name == key {
+			return value, true
+		}
+	}
+	return "", false
+}
+
+// dotenvLine splits a KEY=value line, export prefixes and surrounding quotes are dropped
+func dotenvLine(line string) (string, string, bool) {
+	line = strings.TrimPrefix(strings.TrimSpace(line), "export ")
+	name, value, ok := strings.Cut(line, "=")
+	if !ok {
+		return "", "", false
+	}
+	value = strings.TrimSpace(value)
+	if len(value) >= 2 && (value[0] == '"' || value[0] == '\'') && value[len(value)-1] == value[0] {
+		value = value[1 : len(value)-1]
+	}
+	return strings.TrimSpace(name), value, true
+}
+
+// PatchKeys sets key.path=value assignments in a json secret and pushes the merged document, the changed keys
+// are shown by hash and confirmed unless yes is set
+func PatchKeys(m Manager, secretID string, assignments []string, yes bool) (SetResult, error) {
+	secrets, err := m.Get([]string{secretID})
+	if err != nil {
+		return SetResult{}, err
+	}
+	if len(secrets) == 0 {
+		return SetResult{}, fmt.Errorf("%s: %w", secretID, ErrNotFound)
+	}
+	s := secrets[0]
+	if s.Binary {
+		return SetResult{}, fmt.Errorf("%s is binary, it has no keys to set", s.ID)
+	}
+	var doc map[string]interface{}
+	if err = json.Unmarshal([]byte(s.Content), &doc); err != nil {
+		return SetResult{}, fmt.Errorf("%s is not a json object, push the whole value with jaws set instead", s.ID)
+	}
+
+	fmt.Fprintf(os.Stderr, "patching %s:\n", s.ID)
+	for _, assignment := range assignments {
+		path, value, ok := strings.Cut(assignment, "=")
+		if !ok || path == "" {
+			return SetResult{}, fmt.Errorf("%s is not key.path=value", assignment)
+		}
+		helpers.RegisterSecret(value)
+		if err := helpers.CheckValue(s.ID+"#"+path, value); err != nil {
+			return SetResult{}, err
+		}
+		old, err := ExtractKey(s.Content, path)
+		if err == nil {
+			fmt.Fprintf(os.Stderr, "  %s: %s -> %s\n", path, shortHash([]byte(old)), shortHash([]byte(value)))
+		} else {
+			fmt.Fprintf(os.Stderr, "  %s: %s -> %s\n", path, color.MagentaString("new"), shortHash([]byte(value)))
+		}
+		if err = setKey(doc, strings.Split(path, "."), value); err != nil {
+			return SetResult{}, fmt.Errorf("%s: %w", path, err)
+		}
+	}
+	if !yes {
+		if err = helpers.RequireInteractive("confirming the patch", "pass --yes to push without confirmation"); err != nil {
+			return SetResult{}, err
+		}
+		answer, err := helpers.Prompt(fmt.Sprintf("push %s? [y/N] ", s.ID))
+		if err != nil {
+			return SetResult{}, err
+		}
+		if answer = strings.ToLower(answer); answer != "y" && answer != "yes" {
+			return SetResult{}, errors.New("patch cancelled")
+		}
+	}
+
+	// keep the layout of the stored document, compact or indented
+	var patched []byte
+	if strings.Contains(strings.TrimSpace(s.Content), "\n") {
+		patched, err = json.MarshalIndent(doc, "", "  ")
+	} else {
+		patched, err = json.Marshal(doc)
+	}
+	if err != nil {
+		return SetResult{}, err
+	}
+	if err = m.Put(s.ID, string(patched)); err != nil {
+		return SetResult{}, err
+	}
+	return SetResult{ID: s.ID, Action: ActionUpdated}, nil
+}
+
+// setKey sets the value at path in doc, creating missing objects, a value replacing a number, bool,
+// object or array is parsed as json
+func setKey(doc map[string]interface{}, path []string, value string) error {
+	for _, segment := range path[:len(path)-1] {
+		next, ok := doc[segment]
+		if !ok {
+			next = map[string]interface{}{}
+			doc[segment] = next
+		}
+		obj, ok := next.(map[string]interface{})
+		if !ok {
+			return fmt.Errorf("%s is not an object", segment)
+		}
+		doc = obj
+	}
+	leaf := path[len(path)-1]
+	if old, ok := doc[leaf]; ok {
+		if _, isString := old.(string); !isString {
+			var parsed interface{}
+			if json.Unmarshal([]byte(value), &parsed) == nil {
+				doc[leaf] = parsed
+				return nil
+			}
+		}
+	}
+	doc[leaf] = value
+	return nil
+}
@@ -0,0 +1,99 @@
+package secretsmanager
+
+import (
+	"errors"
+	"testing"
+)
+
+func TestSplitKey(t *testing.T) {
+	tests := []struct {
+		ref  string
+		name string
+		key  string
+	}{
+		{"prod/app/config", "prod/app/config", ""},
+		{"prod/app/config#database.password", "prod/app/config", "database.password"},
+		{"prod/app/config@AWSPREVIOUS#token", "prod/app/config@AWSPREVIOUS", "token"},
+		{"prod/app/config#", "prod/app/config", ""},
+	}
+	for _, tt := range tests {
+		name, key := SplitKey(tt.ref)
+		if name != tt.name || key != tt.key {
+			t.Errorf("SplitKey(%q) = %q, %q, want %q, %q", tt.ref, name, key, tt.name, tt.key)
+		}
+	}
+}
+
+func TestRefSecretID(t *testing.T) {
+	tests := []struct {
+		ref  string
+		want string
+	}{
+		{"prod/app/config", "prod/app/config"},
+		{"prod/app/config#token", "prod/app/config"},
+		{"prod/app/config@AWSPREVIOUS#token", "prod/app/config"},
+		{"user@example.com", "user@example.com"},
+	}
+	for _, tt := range tests {
+		if got := RefSecretID(tt.ref); got != tt.want {
+			t.Errorf("RefSecretID(%q) = %q, want %q", tt.ref, got, tt.want)
+		}
+	}
+}
+
+func TestExtractKey(t *testing.T) {
+	doc := `{"database": {"password": "hunter22", "port": 5432}, "hosts": ["a", "b"], "flag": true}`
+	dotenv := "export API_KEY=\"abc\"\nDEBUG=false\n"
+	tests := []struct {
+		content  string
+		path     string
+		want     string
+		notFound bool
+	}{
+		{doc, "database.password", "hunter22", false},
+		{doc, "database.port", "5432", false},
+		{doc, "database", `{"password":"hunter22","port":5432}`, false},
+		{doc, "hosts.1", "b", false},
+		{doc, "flag", "true", false},
+		{doc, "hosts.2", "", true},
+		{doc, "hosts.x", "", true},
+		{doc, "database.user", "", true},
+		{doc, "flag.value", "", true},
+		{dotenv, "API_KEY", "abc", false},
+		{dotenv, "DEBUG", "false", false},
+		{dotenv, "MISSING", "", true},
+	}
+	for _, tt := range tests {
+		got, err := ExtractKey(tt.content, tt.path)
+		if tt.notFound {
+			if !errors.Is(err, ErrNotFound) {
+				t.Errorf("ExtractKey(%q) error = %v, want ErrNotFound", tt.path, err)
+			}
+			continue
+		}
+		if err != nil || got != tt.want {
+			t.Errorf("ExtractKey(%q) = %q, %v, want %q", tt.path, got, err, tt.want)
+		}
+	}
+}
+
+func TestDotenvLine(t *testing.T) {
+	tests := []struct {
+		line  string
+		name  string
+		value string
+		ok    bool
+	}{
+		{"KEY=value", "KEY", "value", true},
+		{"export KEY='value'", "KEY", "value", true},
+		{` KEY = "a=b" `, "KEY", "a=b", true},
+		{`KEY="unterminated`, "KEY", `"unterminated`, true},
+		{"# comment", "", "", false},
+	}
+	for _, tt := range tests {
+		name, value, ok := dotenvLine(tt.line)
+		if name != tt.name || value != tt.value || ok != tt.ok {
+			t.Errorf("dotenvLine(%q) = %q, %q, %v, want %q, %q, %v", tt.line, name, value, ok, tt.name, tt.value, tt.ok)
+		}
+	}
+}
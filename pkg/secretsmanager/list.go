@@ -4,16 +4,36 @@ import (
 	"context"
 	"log"
 	"sync"
+	"time"
 
-	"github.com/jacbart/jaws/internal/aws"
+	"github.com/aws/aws-sdk-go-v2/aws"
+	"github.com/aws/aws-sdk-go-v2/service/secretsmanager"
+	"github.com/aws/aws-sdk-go-v2/service/secretsmanager/types"
+	jawsaws "github.com/jacbart/jaws/internal/aws"
+	"github.com/jacbart/jaws/utils/helpers"
 	"github.com/ktr0731/go-fuzzyfinder"
 )
 
+// SecretInfo is a listed secret and its metadata
+type SecretInfo struct {
+	ID                string            `json:"id" yaml:"id"`
+	Tags              map[string]string `json:"tags" yaml:"tags"`
+	RotationEnabled   bool              `json:"rotation_enabled" yaml:"rotation_enabled"`
+	RotationLambdaARN string            `json:"rotation_lambda_arn,omitempty" yaml:"rotation_lambda_arn,omitempty"`
+	LastRotated       time.Time         `json:"last_rotated" yaml:"last_rotated"`
+	NextRotation      time.Time         `json:"next_rotation" yaml:"next_rotation"`
+}
+
 func (a *AWSManager) FuzzyFind(ctx context.Context) ([]string, error) {
+	if err := helpers.RequireInteractive("selecting secrets", "pass the secret ids as arguments"); err != nil {
+		return nil, err
+	}
 	var selectedIDs []string
 	var allIDs []string
+	// the finder reads the list under the read lock while the listing appends to it
+	rw := sync.RWMutex{}
 	go func(a *AWSManager, list *[]string) {
-		ctx, cancel := context.WithCancel(context.Background())
+		ctx, cancel := a.context()
 		defer cancel()
 
 		awsClient, err := LoadAWSClient(a, ctx)
@@ -21,33 +41,19 @@ func (a *AWSManager) FuzzyFind(ctx context.Context) ([]string, error) {
 			log.Fatalln(err)
 		}
 
-		var l int
-		listSecretsOutput, err := aws.GetSecretsList(ctx, awsClient, nil)
+		err = a.listSecrets(ctx, awsClient, func(entry types.SecretListEntry) {
+			rw.Lock()
+			*list = append(*list, *entry.Name)
+			rw.Unlock()
+		})
 		if err != nil {
 			log.Fatalln(err)
 		}
-		l = len(listSecretsOutput.SecretList)
-		for i := 0; i < l; i++ {
-			*list = append(*list, *listSecretsOutput.SecretList[i].Name)
-		}
-		for listSecretsOutput.NextToken != nil {
-			listSecretsOutput, err = aws.GetSecretsList(ctx, awsClient, listSecretsOutput.NextToken)
-			if err != nil {
-				log.Fatalln(err)
-			}
-			l = len(listSecretsOutput.SecretList)
-			for i := 0; i < l; i++ {
-				*list = append(*list, *listSecretsOutput.SecretList[i].Name)
-			}
-		}
 	}(a, &allIDs)
 
-	rw := sync.RWMutex{}
-	l := rw.RLocker()
-
-	idxs, _ := fuzzyfinder.FindMulti(&allIDs, func(i int) string {
-		return allIDs[i]
-	}, fuzzyfinder.WithHotReloadLock(l))
+	idxs, _ := a.Finder.find(&allIDs, fuzzyfinder.WithHotReloadLock(rw.RLocker()))
+	rw.RLock()
+	defer rw.RUnlock()
 	for _, idx := range idxs {
 		selectedIDs = append(selectedIDs, allIDs[idx])
 	}
@@ -56,33 +62,108 @@ func (a *AWSManager) FuzzyFind(ctx context.Context) ([]string, error) {
 
 // AWSManager ListAll
 func (a *AWSManager) ListAll() ([]string, error) {
-	ctx, cancel := context.WithCancel(context.Background())
-	defer cancel()
 	var list []string
+	details, err := a.ListDetails()
+	if err != nil {
+		return []string{}, err
+	}
+	for _, s := range details {
+		list = append(list, s.ID)
+	}
+	return list, nil
+}
+
+// AWSManager ListDetails
+func (a *AWSManager) ListDetails() ([]SecretInfo, error) {
+	ctx, cancel := a.context()
+	defer cancel()
+	var list []SecretInfo
 
 	awsClient, err := LoadAWSClient(a, ctx)
 	if err != nil {
-		return []string{}, err
+		return []SecretInfo{}, err
 	}
 
-	var l int
-	listSecretsOutput, err := aws.GetSecretsList(ctx, awsClient, nil)
+	err = a.listSecrets(ctx, awsClient, func(entry types.SecretListEntry) {
+		info := SecretInfo{
+			ID:                *entry.Name,
+			Tags:              awsTags(entry.Tags),
+			RotationEnabled:   entry.RotationEnabled,
+			RotationLambdaARN: aws.ToString(entry.RotationLambdaARN),
+		}
+		info.setRotationDates(entry.LastRotatedDate, entry.RotationRules)
+		list = append(list, info)
+	})
 	if err != nil {
-		return []string{}, err
+		return []SecretInfo{}, err
+	}
+	return list, nil
+}
+
+// AWSManager Describe
+func (a *AWSManager) Describe(secretID string) (SecretInfo, error) {
+	ctx, cancel := a.context()
+	defer cancel()
+
+	awsClient, err := LoadAWSClient(a, ctx)
+	if err != nil {
+		return SecretInfo{}, err
+	}
+
+	out, err := awsClient.DescribeSecret(ctx, &secretsmanager.DescribeSecretInput{
+		SecretId: aws.String(secretID),
+	})
+	if err != nil {
+		return SecretInfo{}, err
 	}
-	l = len(listSecretsOutput.SecretList)
-	for i := 0; i < l; i++ {
-		list = append(list, *listSecretsOutput.SecretList[i].Name)
+	info := SecretInfo{
+		ID:                aws.ToString(out.Name),
+		Tags:              awsTags(out.Tags),
+		RotationEnabled:   out.RotationEnabled,
+		RotationLambdaARN: aws.ToString(out.RotationLambdaARN),
 	}
-	for listSecretsOutput.NextToken != nil {
-		listSecretsOutput, err = aws.GetSecretsList(ctx, awsClient, listSecretsOutput.NextToken)
+	info.setRotationDates(out.LastRotatedDate, out.RotationRules)
+	return info, nil
+}
+
+// setRotationDates fills in the last rotation and, when rotation runs every n days, the next one
+func (s *SecretInfo) setRotationDates(lastRotated *time.Time, rules *types.RotationRulesType) {
+	if lastRotated == nil {
+		return
+	}
+	s.LastRotated = *lastRotated
+	if s.RotationEnabled && rules != nil && rules.AutomaticallyAfterDays > 0 {
+		s.NextRotation = lastRotated.AddDate(0, 0, int(rules.AutomaticallyAfterDays))
+	}
+}
+
+// listSecrets pages through the secrets in the account and calls fn for each one that matches the filter
+func (a *AWSManager) listSecrets(ctx context.Context, client *secretsmanager.Client, fn func(types.SecretListEntry)) error {
+	var nextToken *string
+	for {
+		listSecretsOutput, err := jawsaws.GetSecretsList(ctx, client, nextToken, a.Filter.Prefix)
 		if err != nil {
-			return []string{}, err
+			return err
+		}
+		for _, entry := range listSecretsOutput.SecretList {
+			if a.Filter.Match(*entry.Name, awsTags(entry.Tags)) {
+				fn(entry)
+			}
 		}
-		l = len(listSecretsOutput.SecretList)
-		for i := 0; i < l; i++ {
-			list = append(list, *listSecretsOutput.SecretList[i].Name)
+		if listSecretsOutput.NextToken == nil {
+			return nil
 		}
+		nextToken = listSecretsOutput.NextToken
 	}
-	return list, nil
+}
+
+// awsTags converts secretsmanager tags into a map
+func awsTags(tags []types.Tag) map[string]string {
+	m := make(map[string]string, len(tags))
+	for _, t := range tags {
+		if t.Key != nil && t.Value != nil {
+			m[*t.Key] = *t.Value
+		}
+	}
+	return m
 }
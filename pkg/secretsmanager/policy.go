@@ -0,0 +1,54 @@
+package secretsmanager
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"os"
+
+	"github.com/jacbart/jaws/internal/aws"
+)
+
+// AWSManager GetPolicy
+func (a *AWSManager) GetPolicy(secretID string) (string, error) {
+	ctx, cancel := a.context()
+	defer cancel()
+
+	client, err := LoadAWSClient(a, ctx)
+	if err != nil {
+		return "", err
+	}
+
+	policy, err := aws.GetResourcePolicy(ctx, client, secretID)
+	if err != nil {
+		return "", err
+	}
+	if policy == "" {
+		return "", fmt.Errorf("%s has no resource policy", secretID)
+	}
+	var out bytes.Buffer
+	if err = json.Indent(&out, []byte(policy), "", "  "); err != nil {
+		return policy, nil
+	}
+	return out.String(), nil
+}
+
+// AWSManager SetPolicy
+func (a *AWSManager) SetPolicy(secretID string, policyFile string) error {
+	ctx, cancel := a.context()
+	defer cancel()
+
+	policy, err := os.ReadFile(policyFile)
+	if err != nil {
+		return err
+	}
+	if !json.Valid(policy) {
+		return fmt.Errorf("%s is not valid json", policyFile)
+	}
+
+	client, err := LoadAWSClient(a, ctx)
+	if err != nil {
+		return err
+	}
+	return aws.PutResourcePolicy(ctx, client, secretID, string(policy))
+}
@@ -2,8 +2,11 @@ package secretsmanager
 
 import (
 	"fmt"
+	"sort"
+	"strings"
 
 	"github.com/fatih/color"
+	"github.com/jacbart/jaws/utils/helpers"
 )
 
 func CleanPrintSecrets(Secrets []Secret) {
@@ -14,7 +17,92 @@ func CleanPrintSecrets(Secrets []Secret) {
 
 func FormatPrintSecret(Secrets []Secret) {
 	for _, s := range Secrets {
-		fmt.Printf("Secret ID: %s\n", color.MagentaString(s.ID))
+		id := s.ID
+		if s.Key != "" {
+			id += "#" + s.Key
+		}
+		fmt.Printf("Secret ID: %s\n", color.MagentaString(id))
+		if s.Binary {
+			color.HiYellow("<binary, %d bytes>", len(s.Content))
+			continue
+		}
 		color.HiGreen(s.Content)
 	}
 }
+
+// SecretInfoTable lays out listed secrets as rows of id, tags and rotation
+func SecretInfoTable(details []SecretInfo) helpers.Table {
+	t := helpers.Table{Header: []string{"id", "tags", "rotation"}}
+	for _, s := range details {
+		var tags []string
+		for k, v := range s.Tags {
+			tags = append(tags, fmt.Sprintf("%s=%s", k, v))
+		}
+		sort.Strings(tags)
+		t.AddRow(s.ID, strings.Join(tags, ","), s.rotationSummary())
+	}
+	return t
+}
+
+// PrintRotation prints the rotation configuration of a secret
+func PrintRotation(info SecretInfo) {
+	if !info.RotationEnabled {
+		fmt.Println("rotation: disabled")
+		return
+	}
+	fmt.Printf("rotation: %s\n", color.YellowString("enabled"))
+	fmt.Printf("rotation lambda: %s\n", info.RotationLambdaARN)
+	if !info.LastRotated.IsZero() {
+		fmt.Printf("last rotated: %s\n", info.LastRotated.Format("2006-01-02 15:04:05"))
+	}
+	if !info.NextRotation.IsZero() {
+		fmt.Printf("next rotation: %s\n", info.NextRotation.Format("2006-01-02"))
+	}
+}
+
+func (s SecretInfo) rotationSummary() string {
+	if !s.RotationEnabled {
+		return ""
+	}
+	if s.NextRotation.IsZero() {
+		return "enabled"
+	}
+	return fmt.Sprintf("rotates %s", s.NextRotation.Format("2006-01-02"))
+}
+
+// SetResultsTable lays out set results as rows of id, action and whether the secret is binary
+func SetResultsTable(results []SetResult) helpers.Table {
+	t := helpers.Table{Header: []string{"id", "action", "binary"}}
+	for _, r := range results {
+		t.AddRow(r.ID, r.Action, fmt.Sprint(r.Binary))
+	}
+	return t
+}
+
+// PrintSetResults prints what Set did with each pushed secret
+func PrintSetResults(results []SetResult) {
+	for _, r := range results {
+		action := r.Action
+		if r.Binary && r.Action != ActionSkipped {
+			action += " (binary)"
+		}
+		switch r.Action {
+		case ActionUpdated:
+			action = color.YellowString(action)
+		case ActionCreated:
+			action = color.MagentaString(action)
+		default:
+			action = color.CyanString(action)
+		}
+		fmt.Printf("%s %s\n", r.ID, action)
+	}
+}
+
+// SecretVersionsTable lays out secret versions as rows of version id, creation date and staging labels
+func SecretVersionsTable(versions []SecretVersion) helpers.Table {
+	t := helpers.Table{Header: []string{"version", "created", "stages"}}
+	for _, v := range versions {
+		t.AddRow(v.ID, v.Created.Format("2006-01-02 15:04:05"), strings.Join(v.Stages, ","))
+	}
+	return t
+}
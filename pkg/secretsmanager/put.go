@@ -0,0 +1,25 @@
+package secretsmanager
+
+import (
+	"github.com/jacbart/jaws/internal/aws"
+	"github.com/jacbart/jaws/utils/helpers"
+)
+
+// AWSManager Put updates a single secret with content, or creates it without prompting
+func (a *AWSManager) Put(secretID string, content string) error {
+	if err := helpers.CheckValue(secretID, content); err != nil {
+		return err
+	}
+	ctx, cancel := a.context()
+	defer cancel()
+
+	client, err := LoadAWSClient(a, ctx)
+	if err != nil {
+		return err
+	}
+	_, err = aws.HandleUpdateCreate(ctx, client, secretID, content, true, aws.CreateOptions{
+		KMSKeyID:       a.KMSKeyID,
+		ReplicaRegions: a.ReplicaRegions,
+	})
+	return err
+}
@@ -40,12 +40,24 @@ func (c *JawsConfig) AddConfigPath(path string) {
 	c.FilePaths = append(c.FilePaths, path)
 }
 
+// platforms maps the platform of a manager block to the manager its body is decoded into
+var platforms = map[string]func(profile string) Manager{
+	"aws":        func(profile string) Manager { return &AWSManager{Profile: profile} },
+	"vault":      func(profile string) Manager { return NewVaultManager(profile) },
+	"github":     func(profile string) Manager { return NewGitHubManager(profile) },
+	"gitlab":     func(profile string) Manager { return NewGitLabManager(profile) },
+	"cloudflare": func(profile string) Manager { return NewCloudflareManager(profile) },
+	"heroku":     func(profile string) Manager { return NewHerokuManager(profile) },
+	"fly":        func(profile string) Manager { return NewFlyManager(profile) },
+}
+
 // ReadInConfig
 func (c *JawsConfig) ReadInConfig() (GeneralHCL, []Manager, error) {
 	nilGeneral := &GeneralHCL{
 		DefaultProfile: "",
 		Editor:         "",
 		SecretsPath:    "",
+		VCSRemote:      "",
 	}
 
 	err := checkForConfig(c)
@@ -92,21 +104,17 @@ func (c *JawsConfig) ReadInConfig() (GeneralHCL, []Manager, error) {
 
 	managers := []Manager{}
 	for _, m := range configHCL.Managers {
-		switch managerPlatform := m.Platform; managerPlatform {
-		case "aws":
-			aws := &AWSManager{Profile: m.Profile}
-			if m.Auth != nil {
-				if diag := gohcl.DecodeBody(m.Auth, evalContext, aws); diag.HasErrors() {
-					// return *nilGeneral, nil, fmt.Errorf(
-					// 	"error in ReadConfig decoding aws HCL configuration: %w", diag,
-					// )
-					return *nilGeneral, nil, &DecodeConfigFailed{File: c.CurrentConfig}
-				}
+		newManager, ok := platforms[m.Platform]
+		if !ok {
+			return *nilGeneral, nil, fmt.Errorf("error in ReadConfig: unknown platform `%s`", m.Platform)
+		}
+		manager := newManager(m.Profile)
+		if m.Auth != nil {
+			if diag := gohcl.DecodeBody(m.Auth, evalContext, manager); diag.HasErrors() {
+				return *nilGeneral, nil, &DecodeConfigFailed{File: c.CurrentConfig}
 			}
-			managers = append(managers, aws)
-		default:
-			return *nilGeneral, nil, fmt.Errorf("error in ReadConfig: unknown platform `%s`", managerPlatform)
 		}
+		managers = append(managers, manager)
 	}
 	return configHCL.General, managers, nil
 }
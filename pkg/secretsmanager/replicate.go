@@ -0,0 +1,40 @@
+package secretsmanager
+
+import (
+	"errors"
+	"fmt"
+
+	"github.com/jacbart/jaws/internal/aws"
+)
+
+// AWSManager Replicate
+func (a *AWSManager) Replicate(secretIDs []string, regions []string) error {
+	ctx, cancel := a.context()
+	defer cancel()
+
+	if len(regions) == 0 {
+		regions = a.ReplicaRegions
+	}
+	if len(regions) == 0 {
+		return errors.New("no regions to replicate to, use --regions or set replica_regions in the config")
+	}
+
+	client, err := LoadAWSClient(a, ctx)
+	if err != nil {
+		return err
+	}
+
+	if len(secretIDs) == 0 {
+		secretIDs, err = a.FuzzyFind(ctx)
+		if err != nil {
+			return fmt.Errorf("error while iterating and printing secret names: %v", err)
+		}
+	}
+
+	for _, id := range secretIDs {
+		if err = aws.ReplicateSecret(ctx, client, id, regions); err != nil {
+			return err
+		}
+	}
+	return nil
+}
@@ -0,0 +1,94 @@
+package secretsmanager
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net"
+	"net/http"
+	"strings"
+	"time"
+)
+
+// RESTError is a failed request to the http api of a platform
+type RESTError struct {
+	Method string
+	URL    string
+	Status int
+	Body   string
+}
+
+func (e *RESTError) Error() string {
+	return fmt.Sprintf("%s %s: %d %s", e.Method, e.URL, e.Status, strings.TrimSpace(e.Body))
+}
+
+// isNotFound reports whether err is a 404 from a platform api
+func isNotFound(err error) bool {
+	var restErr *RESTError
+	return errors.As(err, &restErr) && restErr.Status == http.StatusNotFound
+}
+
+// restClient sends json requests to the http api of a platform
+type restClient struct {
+	base    string
+	headers map[string]string
+	http    *http.Client
+}
+
+// defaultRESTTimeout bounds a request when the manager sets no timeout
+const defaultRESTTimeout = 10 * time.Second
+
+// newRESTClient returns a client for base, timeout and connectTimeout are the timeout and
+// connect_timeout of the manager, zero keeps the defaults
+func newRESTClient(base string, headers map[string]string, timeout time.Duration, connectTimeout time.Duration) *restClient {
+	if timeout == 0 {
+		timeout = defaultRESTTimeout
+	}
+	transport := http.DefaultTransport.(*http.Transport).Clone()
+	if connectTimeout != 0 {
+		transport.DialContext = (&net.Dialer{Timeout: connectTimeout, KeepAlive: 30 * time.Second}).DialContext
+	}
+	return &restClient{
+		base:    strings.TrimSuffix(base, "/"),
+		headers: headers,
+		http:    &http.Client{Timeout: timeout, Transport: transport},
+	}
+}
+
+// do sends body as json to path and decodes the response into out, either can be nil
+func (c *restClient) do(method string, path string, body interface{}, out interface{}) error {
+	var reader io.Reader
+	if body != nil {
+		encoded, err := json.Marshal(body)
+		if err != nil {
+			return err
+		}
+		reader = bytes.NewReader(encoded)
+	}
+	req, err := http.NewRequest(method, c.base+path, reader)
+	if err != nil {
+		return err
+	}
+	req.Header.Set("Accept", "application/json")
+	if body != nil {
+		req.Header.Set("Content-Type", "application/json")
+	}
+	for k, v := range c.headers {
+		req.Header.Set(k, v)
+	}
+	resp, err := c.http.Do(req)
+	if err != nil {
+		return err
+	}
+	defer resp.Body.Close()
+	if resp.StatusCode >= 300 {
+		msg, _ := io.ReadAll(io.LimitReader(resp.Body, 4096))
+		return &RESTError{Method: method, URL: c.base + path, Status: resp.StatusCode, Body: string(msg)}
+	}
+	if out == nil || resp.StatusCode == http.StatusNoContent {
+		return nil
+	}
+	return json.NewDecoder(resp.Body).Decode(out)
+}
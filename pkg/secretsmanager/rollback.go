@@ -1,7 +1,6 @@
 package secretsmanager
 
 import (
-	"context"
 	"fmt"
 
 	"github.com/jacbart/jaws/internal/aws"
@@ -9,7 +8,7 @@ import (
 
 // AWSManager Rollback
 func (a *AWSManager) Rollback() error {
-	ctx, cancel := context.WithCancel(context.Background())
+	ctx, cancel := a.context()
 	defer cancel()
 
 	client, err := LoadAWSClient(a, ctx)
@@ -1,60 +1,380 @@
 package secretsmanager
 
 import (
-	"context"
+	"bytes"
+	"crypto/sha256"
+	"encoding/hex"
+	"errors"
 	"fmt"
 	"io/ioutil"
 	"os"
+	"path/filepath"
+	"strings"
+	"text/tabwriter"
+	"unicode/utf8"
 
 	"github.com/fatih/color"
 	"github.com/jacbart/jaws/internal/aws"
+	"github.com/jacbart/jaws/utils/helpers"
 )
 
+// SetOptions change how Set pushes secrets
+type SetOptions struct {
+	SkipCreatePrompt bool
+	KMSKeyID         string
+	Yes              bool
+}
+
+// Actions reported in SetResult for each pushed secret
+const (
+	ActionUpdated = aws.ActionUpdated
+	ActionCreated = aws.ActionCreated
+	ActionSkipped = aws.ActionSkipped
+)
+
+// RemoteValue is the current value of a secret on the platform, Exists is false for secrets that still
+// have to be created and Content is nil on write-only platforms
+type RemoteValue struct {
+	Content []byte
+	Binary  bool
+	Exists  bool
+}
+
+// Changed reports whether updated differs from the remote value
+func (r RemoteValue) Changed(updated []byte) bool {
+	return !r.Exists || !bytes.Equal(r.Content, updated)
+}
+
+// SetResult is what Set did with a pushed secret, one of updated, created or skipped
+type SetResult struct {
+	ID     string `json:"id" yaml:"id"`
+	Action string `json:"action" yaml:"action"`
+	Binary bool   `json:"binary" yaml:"binary"`
+}
+
 // AWSManager Set
-func (a *AWSManager) Set(secretsPath string, createPrompt bool) error {
-	ctx, cancel := context.WithCancel(context.Background())
+func (a *AWSManager) Set(secretsPath string, opts SetOptions) ([]SetResult, error) {
+	ctx, cancel := a.context()
 	defer cancel()
 
 	client, err := LoadAWSClient(a, ctx)
 	if err != nil {
-		return err
+		return nil, err
 	}
 
 	sID, err := aws.GetSecretNames(secretsPath)
 	if err != nil {
-		return err
+		return nil, err
+	}
+
+	createOpts := aws.CreateOptions{
+		KMSKeyID:       a.KMSKeyID,
+		ReplicaRegions: a.ReplicaRegions,
+	}
+	if opts.KMSKeyID != "" {
+		createOpts.KMSKeyID = opts.KMSKeyID
 	}
 
-	l := len(sID)
-	var secretUpdate []byte
-	for i := 0; i < l; i++ {
-		secretUpdate, err = ioutil.ReadFile(fmt.Sprintf("%s/%s", secretsPath, sID[i]))
+	// compare every local secret with its remote value before anything is pushed
+	var changes []setChange
+	for _, id := range sID {
+		local, err := ioutil.ReadFile(fmt.Sprintf("%s/%s", secretsPath, id))
 		if err != nil {
-			return err
+			return nil, err
 		}
-		shouldSecretUpdate, err := aws.CheckIfUpdate(ctx, client, sID[i], string(secretUpdate))
+		helpers.RegisterSecret(string(local))
+		content, binary, exists, err := aws.GetRemoteValue(ctx, client, id)
 		if err != nil {
-			return nil
+			return nil, err
+		}
+		remote := RemoteValue{Content: content, Binary: binary, Exists: exists}
+		changes = append(changes, setChange{id: id, local: local, remote: remote})
+	}
+
+	for i, c := range changes {
+		if !c.remote.Changed(c.local) {
+			continue
 		}
-		if shouldSecretUpdate {
-			if err = aws.HandleUpdateCreate(ctx, client, sID[i], string(secretUpdate), createPrompt); err != nil {
-				return err
+		if utf8.Valid(c.local) {
+			if err = helpers.CheckValue(c.id, string(c.local)); err != nil {
+				return nil, err
 			}
-		} else {
-			fmt.Printf("%s %s\n", sID[i], color.CyanString("skipped"))
 		}
+		if c.remote.Exists {
+			rotating, lastRotated, err := aws.RotationStatus(ctx, client, c.id)
+			if err == nil && rotating {
+				if err = helpers.CheckRotation(c.id, lastRotated); err != nil {
+					return nil, err
+				}
+				changes[i].rotating = true
+			}
+		}
+	}
+
+	skipCreatePrompt := opts.SkipCreatePrompt
+	if !opts.Yes && hasChanges(changes) {
+		if err = a.confirmSet(changes); err != nil {
+			return nil, err
+		}
+		// new secrets were listed in the confirmed summary
+		skipCreatePrompt = true
+	}
+
+	var results []SetResult
+	for _, c := range changes {
+		result := SetResult{
+			ID:     c.id,
+			Action: ActionSkipped,
+		}
+		if c.remote.Changed(c.local) {
+			if c.rotating {
+				fmt.Fprintln(os.Stderr, color.YellowString("%s has managed rotation enabled, the next rotation will replace the pushed value", c.id))
+			}
+			secretOpts := createOpts
+			secretOpts.Binary = c.remote.Binary || !utf8.Valid(c.local)
+			result.Binary = secretOpts.Binary
+			if result.Action, err = aws.HandleUpdateCreate(ctx, client, c.id, string(c.local), skipCreatePrompt, secretOpts); err != nil {
+				return results, err
+			}
+		}
+		results = append(results, result)
+	}
+	return results, nil
+}
+
+// setChange is a local secret and the remote value it replaces
+type setChange struct {
+	id       string
+	local    []byte
+	remote   RemoteValue
+	rotating bool
+}
+
+func hasChanges(changes []setChange) bool {
+	for _, c := range changes {
+		if c.remote.Changed(c.local) {
+			return true
+		}
+	}
+	return false
+}
+
+// confirmSet confirms the changes with the profile, region and role they are pushed to
+func (a *AWSManager) confirmSet(changes []setChange) error {
+	target := fmt.Sprintf("profile %s", a.Profile)
+	if a.Region != "" {
+		target += fmt.Sprintf(", region %s", a.Region)
+	}
+	if a.RoleARN != "" {
+		target += fmt.Sprintf(", role %s", a.RoleARN)
+	}
+	return confirmChanges(target, changes)
+}
+
+// confirmChanges prints the old and new hash and the size change of every changed secret and the target
+// they go to, then asks before anything is pushed
+func confirmChanges(target string, changes []setChange) error {
+	if err := helpers.RequireInteractive("confirming the push", "pass --yes to push without confirmation"); err != nil {
+		return err
+	}
+	fmt.Fprintf(os.Stderr, "pushing to %s:\n", target)
+	w := tabwriter.NewWriter(os.Stderr, 0, 0, 2, ' ', 0)
+	count := 0
+	for _, c := range changes {
+		if !c.remote.Changed(c.local) {
+			continue
+		}
+		count++
+		if !c.remote.Exists {
+			fmt.Fprintf(w, "  %s\t%s\t%s\t%d bytes\n", c.id, color.MagentaString("new"), shortHash(c.local), len(c.local))
+			continue
+		}
+		// write-only platforms never return the stored value
+		if c.remote.Content == nil {
+			fmt.Fprintf(w, "  %s\t%s\t%s\t%d bytes\n", c.id, color.YellowString("write-only"), shortHash(c.local), len(c.local))
+			continue
+		}
+		fmt.Fprintf(w, "  %s\t%s\t%s\t%+d bytes\n", c.id, shortHash(c.remote.Content), shortHash(c.local), len(c.local)-len(c.remote.Content))
+	}
+	if err := w.Flush(); err != nil {
+		return err
+	}
+	userResponse, err := helpers.Prompt(fmt.Sprintf("push %d secret(s)? [y/N] ", count))
+	if err != nil {
+		return err
+	}
+	userResponse = strings.ToLower(userResponse)
+	if userResponse != "y" && userResponse != "yes" {
+		return errors.New("push cancelled")
 	}
 	return nil
 }
 
-// SetPostRun
-func SetPostRun(secretsPath string, cleanLocalSecrets bool) error {
-	if !cleanLocalSecrets {
-		err := os.RemoveAll(secretsPath)
+// pushFolder pushes the secrets folder for managers without a native Set, remote returns the stored
+// value of a secret and put writes a changed one
+func pushFolder(secretsPath string, target string, opts SetOptions, remote func(string) (RemoteValue, error), put func(string, string) error) ([]SetResult, error) {
+	changes, err := planFolder(secretsPath, target, opts, remote)
+	if err != nil {
+		return nil, err
+	}
+	var results []SetResult
+	for _, c := range changes {
+		if c.remote.Changed(c.local) {
+			if err = put(c.id, string(c.local)); err != nil {
+				return results, err
+			}
+		}
+		results = append(results, c.result())
+	}
+	return results, nil
+}
+
+// pushFolderBatch is pushFolder for platforms that roll out a release on every write, put gets every
+// changed secret at once
+func pushFolderBatch(secretsPath string, target string, opts SetOptions, remote func(string) (RemoteValue, error), put func(map[string]string) error) ([]SetResult, error) {
+	changes, err := planFolder(secretsPath, target, opts, remote)
+	if err != nil {
+		return nil, err
+	}
+	changed := map[string]string{}
+	for _, c := range changes {
+		if c.remote.Changed(c.local) {
+			changed[c.id] = string(c.local)
+		}
+	}
+	if len(changed) != 0 {
+		if err = put(changed); err != nil {
+			return nil, err
+		}
+	}
+	var results []SetResult
+	for _, c := range changes {
+		results = append(results, c.result())
+	}
+	return results, nil
+}
+
+// result is what pushing the change did
+func (c setChange) result() SetResult {
+	switch {
+	case !c.remote.Changed(c.local):
+		return SetResult{ID: c.id, Action: ActionSkipped}
+	case !c.remote.Exists:
+		return SetResult{ID: c.id, Action: ActionCreated}
+	default:
+		return SetResult{ID: c.id, Action: ActionUpdated}
+	}
+}
+
+// planFolder compares every secret in the secrets folder with its remote value and confirms the changes
+// unless opts.Yes is set
+func planFolder(secretsPath string, target string, opts SetOptions, remote func(string) (RemoteValue, error)) ([]setChange, error) {
+	sID, err := aws.GetSecretNames(secretsPath)
+	if err != nil {
+		return nil, err
+	}
+	var changes []setChange
+	for _, id := range sID {
+		local, err := ioutil.ReadFile(fmt.Sprintf("%s/%s", secretsPath, id))
+		if err != nil {
+			return nil, err
+		}
+		helpers.RegisterSecret(string(local))
+		value, err := remote(id)
 		if err != nil {
-			return nil
+			return nil, err
+		}
+		if value.Changed(local) {
+			if err = helpers.CheckValue(id, string(local)); err != nil {
+				return nil, err
+			}
+		}
+		changes = append(changes, setChange{id: id, local: local, remote: value})
+	}
+	if !opts.Yes && hasChanges(changes) {
+		if err = confirmChanges(target, changes); err != nil {
+			return nil, err
+		}
+	}
+	return changes, nil
+}
+
+// shortHash identifies a secret value without showing it
+func shortHash(content []byte) string {
+	sum := sha256.Sum256(content)
+	return hex.EncodeToString(sum[:])[:8]
+}
+
+// SetPreRun validates the secrets about to be pushed, only the edited ones when the secrets folder is
+// under version control
+func SetPreRun(secretsPath string, validators helpers.Validators, noVCS bool) error {
+	var sID []string
+	var err error
+	if !noVCS && helpers.CheckIfGitRepo(secretsPath, false) {
+		sID, err = helpers.EditedSecrets(secretsPath)
+	} else {
+		sID, err = aws.GetSecretNames(secretsPath)
+	}
+	if err != nil {
+		return err
+	}
+	return validators.ValidateAll(secretsPath, sID)
+}
+
+// SetCommit commits the secrets a set created or updated in the secrets folder, it runs after the push
+// so a cancelled or failed push is not recorded as a set
+func SetCommit(secretsPath string, profile string, results []SetResult, noVCS bool) error {
+	if noVCS || !helpers.CheckIfGitRepo(secretsPath, false) {
+		return nil
+	}
+	var sID []string
+	for _, r := range results {
+		if r.Action != ActionSkipped {
+			sID = append(sID, r.ID)
+		}
+	}
+	if len(sID) == 0 {
+		return nil
+	}
+	return helpers.GitControlSecrets(helpers.Snapshot{
+		Command:   "set",
+		Profile:   profile,
+		SecretIDs: sID,
+	}, secretsPath)
+}
+
+// SetPostRun removes the pushed secrets unless they are kept. The .git folder stays and the removal is
+// committed as a clean snapshot so git status, jaws diff and the clean worktree checks don't see every
+// secret as deleted
+func SetPostRun(secretsPath string, profile string, cleanLocalSecrets bool, noVCS bool) error {
+	if cleanLocalSecrets {
+		return nil
+	}
+	sID, err := aws.GetSecretNames(secretsPath)
+	if err != nil {
+		return err
+	}
+	entries, err := os.ReadDir(secretsPath)
+	if err != nil {
+		return err
+	}
+	for _, e := range entries {
+		if e.Name() == ".git" {
+			continue
+		}
+		if err = os.RemoveAll(filepath.Join(secretsPath, e.Name())); err != nil {
+			return err
+		}
+	}
+	if !noVCS && len(sID) != 0 && helpers.CheckIfGitRepo(secretsPath, false) {
+		if err = helpers.GitControlSecrets(helpers.Snapshot{
+			Command:   "set cleanup",
+			Profile:   profile,
+			SecretIDs: sID,
+		}, secretsPath); err != nil {
+			return err
 		}
-		color.Red("folder '%s' deleted\n", secretsPath)
 	}
+	color.Red("secrets in '%s' deleted\n", secretsPath)
 	return nil
 }
@@ -0,0 +1,467 @@
+package secretsmanager
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io/ioutil"
+	"os"
+	"path/filepath"
+	"reflect"
+	"sort"
+	"strconv"
+	"strings"
+	"time"
+
+	"github.com/fatih/color"
+	"github.com/jacbart/jaws/utils/helpers"
+)
+
+// VaultManager keeps secrets in a kv v1 or v2 engine of HashiCorp Vault
+type VaultManager struct {
+	baseManager
+	Address        string `hcl:"address,optional"`
+	Mount          string `hcl:"mount,optional"`
+	KVVersion      int    `hcl:"kv_version,optional"`
+	Namespace      string `hcl:"namespace,optional"`
+	TokenEnv       string `hcl:"token_env,optional"`
+	Timeout        string `hcl:"timeout,optional"`
+	ConnectTimeout string `hcl:"connect_timeout,optional"`
+}
+
+// vaultMetadata is the version list a kv v2 engine keeps for a secret
+type vaultMetadata struct {
+	Data struct {
+		CurrentVersion int `json:"current_version"`
+		Versions       map[string]struct {
+			CreatedTime  time.Time `json:"created_time"`
+			DeletionTime string    `json:"deletion_time"`
+			Destroyed    bool      `json:"destroyed"`
+		} `json:"versions"`
+	} `json:"data"`
+}
+
+// NewVaultManager returns a vault manager for profile with the default mount and kv version
+func NewVaultManager(profile string) *VaultManager {
+	return &VaultManager{baseManager: baseManager{Platform: "vault", Profile: profile}}
+}
+
+// client connects to Address, or VAULT_ADDR, with the token in TokenEnv, or VAULT_TOKEN
+func (v *VaultManager) client() (*restClient, error) {
+	addr := v.Address
+	if addr == "" {
+		addr = os.Getenv("VAULT_ADDR")
+	}
+	if addr == "" {
+		return nil, fmt.Errorf("vault profile %s has no address and VAULT_ADDR is not set", v.Profile)
+	}
+	tokenEnv := v.TokenEnv
+	if tokenEnv == "" {
+		tokenEnv = "VAULT_TOKEN"
+	}
+	token := os.Getenv(tokenEnv)
+	if token == "" {
+		return nil, &MissingTokenError{Env: tokenEnv, Platform: "vault", Profile: v.Profile}
+	}
+	headers := map[string]string{"X-Vault-Token": token}
+	if v.Namespace != "" {
+		headers["X-Vault-Namespace"] = v.Namespace
+	}
+	timeout, connectTimeout, err := parseTimeouts(v.Profile, v.Timeout, v.ConnectTimeout)
+	if err != nil {
+		return nil, err
+	}
+	return newRESTClient(addr+"/v1", headers, timeout, connectTimeout), nil
+}
+
+func (v *VaultManager) mount() string {
+	if v.Mount == "" {
+		return "secret"
+	}
+	return strings.Trim(v.Mount, "/")
+}
+
+func (v *VaultManager) v2() bool {
+	return v.KVVersion != 1
+}
+
+// dataPath is where the value of a secret is read and written
+func (v *VaultManager) dataPath(name string) string {
+	if v.v2() {
+		return fmt.Sprintf("/%s/data/%s", v.mount(), name)
+	}
+	return fmt.Sprintf("/%s/%s", v.mount(), name)
+}
+
+// metadataPath is where a secret is listed and, for kv v2, where its versions are kept
+func (v *VaultManager) metadataPath(name string) string {
+	if v.v2() {
+		return fmt.Sprintf("/%s/metadata/%s", v.mount(), name)
+	}
+	return fmt.Sprintf("/%s/%s", v.mount(), name)
+}
+
+// listKeys walks the folders below folder and returns every secret in them
+func (v *VaultManager) listKeys(c *restClient, folder string) ([]string, error) {
+	var resp struct {
+		Data struct {
+			Keys []string `json:"keys"`
+		} `json:"data"`
+	}
+	if err := c.do("GET", v.metadataPath(folder)+"?list=true", nil, &resp); err != nil {
+		if isNotFound(err) {
+			return nil, nil
+		}
+		return nil, err
+	}
+	var keys []string
+	for _, k := range resp.Data.Keys {
+		if strings.HasSuffix(k, "/") {
+			sub, err := v.listKeys(c, folder+k)
+			if err != nil {
+				return nil, err
+			}
+			keys = append(keys, sub...)
+			continue
+		}
+		keys = append(keys, folder+k)
+	}
+	return keys, nil
+}
+
+// read returns the value of a secret, version 0 is the current one
+func (v *VaultManager) read(c *restClient, name string, version int) (string, bool, error) {
+	data, exists, err := v.readData(c, name, version)
+	if err != nil || !exists {
+		return "", exists, err
+	}
+	content, err := vaultContent(data)
+	return content, true, err
+}
+
+// readData returns the key value pairs of a secret at version, 0 reads the current one
+func (v *VaultManager) readData(c *restClient, name string, version int) (map[string]interface{}, bool, error) {
+	var data map[string]interface{}
+	var err error
+	if v.v2() {
+		var resp struct {
+			Data struct {
+				Data map[string]interface{} `json:"data"`
+			} `json:"data"`
+		}
+		path := v.dataPath(name)
+		if version != 0 {
+			path += fmt.Sprintf("?version=%d", version)
+		}
+		err = c.do("GET", path, nil, &resp)
+		data = resp.Data.Data
+	} else {
+		var resp struct {
+			Data map[string]interface{} `json:"data"`
+		}
+		err = c.do("GET", v.dataPath(name), nil, &resp)
+		data = resp.Data
+	}
+	if isNotFound(err) {
+		return nil, false, nil
+	} else if err != nil {
+		return nil, false, err
+	}
+	return data, true, nil
+}
+
+// write stores content as the new value of a secret
+func (v *VaultManager) write(c *restClient, name string, content string) error {
+	data := vaultData(content)
+	if v.v2() {
+		return c.do("POST", v.dataPath(name), map[string]interface{}{"data": data}, nil)
+	}
+	return c.do("POST", v.dataPath(name), data, nil)
+}
+
+// vaultContent turns the key value pairs of a secret into its content, a lone value key is unwrapped
+func vaultContent(data map[string]interface{}) (string, error) {
+	if value, ok := data["value"].(string); ok && len(data) == 1 {
+		return value, nil
+	}
+	content, err := json.MarshalIndent(data, "", "  ")
+	if err != nil {
+		return "", err
+	}
+	return string(content), nil
+}
+
+// vaultData turns content into the key value pairs vault stores, anything but a json object goes in a value key
+func vaultData(content string) map[string]interface{} {
+	var data map[string]interface{}
+	if err := json.Unmarshal([]byte(content), &data); err == nil {
+		return data
+	}
+	return map[string]interface{}{"value": content}
+}
+
+// splitVaultVersion splits name@3 into the secret name and version
+func splitVaultVersion(ref string) (string, int) {
+	i := strings.LastIndex(ref, "@")
+	if i == -1 {
+		return ref, 0
+	}
+	version, err := strconv.Atoi(ref[i+1:])
+	if err != nil {
+		return ref, 0
+	}
+	return ref[:i], version
+}
+
+// metadata returns the versions of a kv v2 secret
+func (v *VaultManager) metadata(c *restClient, name string) (vaultMetadata, error) {
+	var meta vaultMetadata
+	if !v.v2() {
+		return meta, v.unsupported("versions on kv v1")
+	}
+	err := c.do("GET", v.metadataPath(name), nil, &meta)
+	return meta, err
+}
+
+// VaultManager Get
+func (v *VaultManager) Get(secretsIDList []string) ([]Secret, error) {
+	c, err := v.client()
+	if err != nil {
+		return nil, err
+	}
+	secretIDs := secretsIDList
+	if len(secretIDs) == 0 {
+		if secretIDs, err = v.FuzzyFind(context.Background()); err != nil {
+			return nil, err
+		}
+	}
+	var secrets []Secret
+	for _, id := range secretIDs {
+		// name@3 pins a version and name#database.password returns a single key, like on aws
+		ref, key := SplitKey(id)
+		name, version := splitVaultVersion(ref)
+		content, exists, err := v.read(c, name, version)
+		if err != nil {
+			return nil, err
+		}
+		if !exists {
+			fmt.Fprintf(os.Stderr, "%s %s\n", color.RedString("no secret found called"), color.RedString(name))
+			continue
+		}
+		helpers.RegisterSecret(content)
+		if key != "" {
+			if content, err = ExtractKey(content, key); err != nil {
+				return nil, fmt.Errorf("%s: %w", name, err)
+			}
+		}
+		secrets = append(secrets, Secret{ID: name, Key: key, Content: content})
+	}
+	return secrets, nil
+}
+
+// VaultManager ListDetails
+func (v *VaultManager) ListDetails() ([]SecretInfo, error) {
+	c, err := v.client()
+	if err != nil {
+		return nil, err
+	}
+	keys, err := v.listKeys(c, "")
+	if err != nil {
+		return nil, err
+	}
+	var list []SecretInfo
+	for _, k := range keys {
+		if v.Filter.Match(k, nil) {
+			list = append(list, SecretInfo{ID: k})
+		}
+	}
+	return list, nil
+}
+
+// VaultManager ListAll
+func (v *VaultManager) ListAll() ([]string, error) {
+	details, err := v.ListDetails()
+	if err != nil {
+		return nil, err
+	}
+	var list []string
+	for _, s := range details {
+		list = append(list, s.ID)
+	}
+	return list, nil
+}
+
+func (v *VaultManager) FuzzyFind(ctx context.Context) ([]string, error) {
+	ids, err := v.ListAll()
+	if err != nil {
+		return nil, err
+	}
+	return v.fuzzyFind(ids)
+}
+
+// VaultManager Put
+func (v *VaultManager) Put(secretID string, content string) error {
+	if err := helpers.CheckValue(secretID, content); err != nil {
+		return err
+	}
+	c, err := v.client()
+	if err != nil {
+		return err
+	}
+	return v.write(c, secretID, content)
+}
+
+// VaultManager Set
+func (v *VaultManager) Set(secretsPath string, opts SetOptions) ([]SetResult, error) {
+	c, err := v.client()
+	if err != nil {
+		return nil, err
+	}
+	// vault keeps key value pairs, not the formatting of the file, so the parsed pairs are compared
+	remote := func(id string) (RemoteValue, error) {
+		data, exists, err := v.readData(c, id, 0)
+		if err != nil || !exists {
+			return RemoteValue{Exists: exists}, err
+		}
+		local, err := ioutil.ReadFile(filepath.Join(secretsPath, id))
+		if err != nil {
+			return RemoteValue{}, err
+		}
+		if reflect.DeepEqual(vaultData(string(local)), data) {
+			return RemoteValue{Content: local, Exists: true}, nil
+		}
+		content, err := vaultContent(data)
+		return RemoteValue{Content: []byte(content), Exists: true}, err
+	}
+	put := func(id string, content string) error {
+		return v.write(c, id, content)
+	}
+	return pushFolder(secretsPath, fmt.Sprintf("vault profile %s, mount %s", v.Profile, v.mount()), opts, remote, put)
+}
+
+// VaultManager Erase deletes a secret and, on kv v2, all of its versions
+func (v *VaultManager) Erase(secretID string) error {
+	c, err := v.client()
+	if err != nil {
+		return err
+	}
+	return c.do("DELETE", v.metadataPath(secretID), nil, nil)
+}
+
+// VaultManager Delete deletes the current version of the selected secrets, kv v2 keeps it restorable
+func (v *VaultManager) Delete(int64) error {
+	c, err := v.client()
+	if err != nil {
+		return err
+	}
+	ids, err := v.FuzzyFind(context.Background())
+	if err != nil {
+		return err
+	}
+	for _, id := range ids {
+		if err = c.do("DELETE", v.dataPath(id), nil, nil); err != nil {
+			return err
+		}
+		fmt.Printf("%s %s\n", id, color.RedString("deleted"))
+	}
+	return nil
+}
+
+// VaultManager DeleteCancel undeletes the current version of the given kv v2 secrets, or the selected ones
+func (v *VaultManager) DeleteCancel(args []string) error {
+	if !v.v2() {
+		return v.unsupported("delete cancel on kv v1")
+	}
+	c, err := v.client()
+	if err != nil {
+		return err
+	}
+	ids := args
+	if len(ids) == 0 {
+		if ids, err = v.FuzzyFind(context.Background()); err != nil {
+			return err
+		}
+	}
+	for _, id := range ids {
+		meta, err := v.metadata(c, id)
+		if err != nil {
+			return err
+		}
+		body := map[string]interface{}{"versions": []int{meta.Data.CurrentVersion}}
+		if err = c.do("POST", fmt.Sprintf("/%s/undelete/%s", v.mount(), id), body, nil); err != nil {
+			return err
+		}
+		fmt.Printf("%s %s\n", id, color.GreenString("restored"))
+	}
+	return nil
+}
+
+// VaultManager Rollback writes the previous version of the selected kv v2 secrets as a new version
+func (v *VaultManager) Rollback() error {
+	if !v.v2() {
+		return v.unsupported("rollback on kv v1")
+	}
+	c, err := v.client()
+	if err != nil {
+		return err
+	}
+	ids, err := v.FuzzyFind(context.Background())
+	if err != nil {
+		return err
+	}
+	for _, id := range ids {
+		meta, err := v.metadata(c, id)
+		if err != nil {
+			return err
+		}
+		previous := meta.Data.CurrentVersion - 1
+		if previous < 1 {
+			return fmt.Errorf("%s has no previous version to roll back to", id)
+		}
+		content, exists, err := v.read(c, id, previous)
+		if err != nil {
+			return err
+		}
+		if !exists {
+			return fmt.Errorf("%s has no previous version to roll back to", id)
+		}
+		if err = v.write(c, id, content); err != nil {
+			return err
+		}
+		fmt.Printf("%s %s to version %d\n", id, color.YellowString("rolled back"), previous)
+	}
+	return nil
+}
+
+// VaultManager History
+func (v *VaultManager) History(secretID string) ([]SecretVersion, error) {
+	c, err := v.client()
+	if err != nil {
+		return nil, err
+	}
+	meta, err := v.metadata(c, secretID)
+	if err != nil {
+		return nil, err
+	}
+	var versions []SecretVersion
+	for id, version := range meta.Data.Versions {
+		var stages []string
+		switch {
+		case id == strconv.Itoa(meta.Data.CurrentVersion):
+			stages = append(stages, "current")
+		case version.Destroyed:
+			stages = append(stages, "destroyed")
+		case version.DeletionTime != "":
+			stages = append(stages, "deleted")
+		}
+		versions = append(versions, SecretVersion{ID: id, Stages: stages, Created: version.CreatedTime})
+	}
+	sort.Slice(versions, func(i, j int) bool {
+		return versions[i].Created.After(versions[j].Created)
+	})
+	if len(versions) == 0 {
+		return nil, errors.New("no versions found for " + secretID)
+	}
+	return versions, nil
+}
@@ -0,0 +1,179 @@
+package helpers
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"runtime"
+	"strings"
+	"text/template"
+
+	"github.com/fatih/color"
+)
+
+const (
+	agentUnitName  = "jaws-watch.service"
+	agentPlistName = "com.jacbart.jaws.watch"
+)
+
+// AgentOptions is the command the installed agent runs and the environment it runs with
+type AgentOptions struct {
+	Executable string
+	Args       []string
+	Env        map[string]string
+}
+
+var systemdUnit = template.Must(template.New("unit").Parse(`[Unit]
+Description=jaws watch, commits edited secrets to the secrets folder history
+
+[Service]
+ExecStart="{{ .Executable }}"{{ range .Args }} "{{ . }}"{{ end }}
+{{- range $k, $v := .Env }}
+Environment="{{ $k }}={{ $v }}"
+{{- end }}
+Restart=on-failure
+
+[Install]
+WantedBy=default.target
+`))
+
+var launchdPlist = template.Must(template.New("plist").Parse(`<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>Label</key>
+  <string>` + agentPlistName + `</string>
+  <key>ProgramArguments</key>
+  <array>
+    <string>{{ .Executable }}</string>
+{{- range .Args }}
+    <string>{{ . }}</string>
+{{- end }}
+  </array>
+  <key>EnvironmentVariables</key>
+  <dict>
+{{- range $k, $v := .Env }}
+    <key>{{ $k }}</key>
+    <string>{{ $v }}</string>
+{{- end }}
+  </dict>
+  <key>RunAtLoad</key>
+  <true/>
+  <key>KeepAlive</key>
+  <true/>
+</dict>
+</plist>
+`))
+
+// agentFile returns the path of the systemd user unit or launchd plist of the agent
+func agentFile() (string, error) {
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return "", err
+	}
+	switch runtime.GOOS {
+	case "linux":
+		return filepath.Join(home, ".config", "systemd", "user", agentUnitName), nil
+	case "darwin":
+		return filepath.Join(home, "Library", "LaunchAgents", agentPlistName+".plist"), nil
+	}
+	return "", fmt.Errorf("jaws agent supports systemd and launchd, not %s", runtime.GOOS)
+}
+
+// InstallAgent writes the systemd user unit, or launchd plist on macOS, for the agent and starts it
+func InstallAgent(opts AgentOptions) error {
+	path, err := agentFile()
+	if err != nil {
+		return err
+	}
+	if err = os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+		return err
+	}
+	f, err := os.Create(path)
+	if err != nil {
+		return err
+	}
+	defer f.Close()
+	tmpl := systemdUnit
+	if runtime.GOOS == "darwin" {
+		tmpl = launchdPlist
+	}
+	if err = tmpl.Execute(f, opts); err != nil {
+		return err
+	}
+	if err = f.Close(); err != nil {
+		return err
+	}
+
+	if runtime.GOOS == "darwin" {
+		err = runAgentCommand("launchctl", "load", "-w", path)
+	} else {
+		if err = runAgentCommand("systemctl", "--user", "daemon-reload"); err != nil {
+			return err
+		}
+		err = runAgentCommand("systemctl", "--user", "enable", "--now", agentUnitName)
+	}
+	if err != nil {
+		return err
+	}
+	fmt.Printf("%s %s\n", path, color.GreenString("installed"))
+	return nil
+}
+
+// UninstallAgent stops the agent and removes its unit or plist
+func UninstallAgent() error {
+	path, err := agentFile()
+	if err != nil {
+		return err
+	}
+	if _, err = os.Stat(path); os.IsNotExist(err) {
+		return fmt.Errorf("no agent installed at %s", path)
+	}
+	if runtime.GOOS == "darwin" {
+		err = runAgentCommand("launchctl", "unload", "-w", path)
+	} else {
+		err = runAgentCommand("systemctl", "--user", "disable", "--now", agentUnitName)
+	}
+	if err != nil {
+		return err
+	}
+	if err = os.Remove(path); err != nil {
+		return err
+	}
+	if runtime.GOOS != "darwin" {
+		if err = runAgentCommand("systemctl", "--user", "daemon-reload"); err != nil {
+			return err
+		}
+	}
+	fmt.Printf("%s %s\n", path, color.RedString("removed"))
+	return nil
+}
+
+// AgentStatus prints whether the agent is installed and what the service manager reports for it
+func AgentStatus() error {
+	path, err := agentFile()
+	if err != nil {
+		return err
+	}
+	if _, err = os.Stat(path); os.IsNotExist(err) {
+		fmt.Printf("no agent installed at %s\n", path)
+		return nil
+	}
+	fmt.Printf("agent installed at %s\n", path)
+	if runtime.GOOS == "darwin" {
+		return runAgentCommand("launchctl", "list", agentPlistName)
+	}
+	return runAgentCommand("systemctl", "--user", "--no-pager", "status", agentUnitName)
+}
+
+// runAgentCommand runs a systemctl or launchctl command with its output passed through
+func runAgentCommand(name string, args ...string) error {
+	c := exec.Command(name, args...)
+	c.Stdout = os.Stdout
+	c.Stderr = os.Stderr
+	if err := c.Run(); err != nil {
+		return fmt.Errorf("%s %s: %w", name, strings.Join(args, " "), err)
+	}
+	return nil
+}
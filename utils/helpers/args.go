@@ -0,0 +1,30 @@
+package helpers
+
+import (
+	"bufio"
+	"errors"
+	"os"
+	"strings"
+)
+
+// StdinArgs replaces a lone `-` argument with the newline separated secret ids piped into stdin,
+// any other arguments are returned unchanged
+func StdinArgs(args []string) ([]string, error) {
+	if len(args) != 1 || args[0] != "-" {
+		return args, nil
+	}
+	var ids []string
+	scanner := bufio.NewScanner(os.Stdin)
+	for scanner.Scan() {
+		if id := strings.TrimSpace(scanner.Text()); id != "" {
+			ids = append(ids, id)
+		}
+	}
+	if err := scanner.Err(); err != nil {
+		return nil, err
+	}
+	if len(ids) == 0 {
+		return nil, errors.New("no secret ids read from stdin")
+	}
+	return ids, nil
+}
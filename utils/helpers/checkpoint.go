@@ -0,0 +1,147 @@
+package helpers
+
+import (
+	"errors"
+	"fmt"
+	"path/filepath"
+
+	"github.com/fatih/color"
+	"github.com/go-git/go-git/v5"
+	"github.com/go-git/go-git/v5/plumbing"
+	"github.com/go-git/go-git/v5/plumbing/object"
+)
+
+// CreateCheckpoint tags the current commit of the secrets folder with name
+func CreateCheckpoint(secretsPath string, name string) error {
+	repo, err := git.PlainOpen(secretsPath)
+	if err != nil {
+		return fmt.Errorf("opening secrets repo: %w", err)
+	}
+	head, err := repo.Head()
+	if err != nil {
+		return err
+	}
+	if _, err = repo.CreateTag(name, head.Hash(), nil); err != nil {
+		if errors.Is(err, git.ErrTagExists) {
+			return fmt.Errorf("checkpoint %s already exists", name)
+		}
+		return err
+	}
+	fmt.Printf("checkpoint %s %s at %s\n", name, color.GreenString("created"), head.Hash().String()[:8])
+	return nil
+}
+
+// ListCheckpoints prints every checkpoint in the secrets folder and the commit it points to
+func ListCheckpoints(secretsPath string) error {
+	repo, err := git.PlainOpen(secretsPath)
+	if err != nil {
+		return fmt.Errorf("opening secrets repo: %w", err)
+	}
+	iter, err := repo.Tags()
+	if err != nil {
+		return err
+	}
+	return iter.ForEach(func(ref *plumbing.Reference) error {
+		commit, err := checkpointCommit(repo, ref)
+		if err != nil {
+			return err
+		}
+		fmt.Printf("%s ", color.MagentaString(ref.Name().Short()))
+		printCommit(commit)
+		return nil
+	})
+}
+
+// RestoreCheckpoint writes the secrets from checkpoint name back into the secrets folder and commits the result,
+// the commits made since the checkpoint are kept in the history, a folder with uncommitted changes is refused
+func RestoreCheckpoint(secretsPath string, name string, profile string) error {
+	repo, err := git.PlainOpen(secretsPath)
+	if err != nil {
+		return fmt.Errorf("opening secrets repo: %w", err)
+	}
+	ref, err := repo.Tag(name)
+	if err != nil {
+		if errors.Is(err, git.ErrTagNotFound) {
+			return fmt.Errorf("no checkpoint called %s", name)
+		}
+		return err
+	}
+	commit, err := checkpointCommit(repo, ref)
+	if err != nil {
+		return err
+	}
+	tree, err := commit.Tree()
+	if err != nil {
+		return err
+	}
+	w, err := repo.Worktree()
+	if err != nil {
+		return err
+	}
+	if err = requireClean(w, "restoring a checkpoint"); err != nil {
+		return err
+	}
+
+	// remove secrets that were added after the checkpoint
+	head, err := repo.Head()
+	if err != nil {
+		return err
+	}
+	headCommit, err := repo.CommitObject(head.Hash())
+	if err != nil {
+		return err
+	}
+	headTree, err := headCommit.Tree()
+	if err != nil {
+		return err
+	}
+	err = headTree.Files().ForEach(func(f *object.File) error {
+		if _, err := tree.File(f.Name); errors.Is(err, object.ErrFileNotFound) {
+			_, err = w.Remove(f.Name)
+			return err
+		}
+		return nil
+	})
+	if err != nil {
+		return err
+	}
+
+	var restored []string
+	err = tree.Files().ForEach(func(f *object.File) error {
+		contents, err := f.Contents()
+		if err != nil {
+			return err
+		}
+		path := filepath.Join(secretsPath, f.Name)
+		if err = WriteSecretFile(path, []byte(contents)); err != nil {
+			return err
+		}
+		restored = append(restored, f.Name)
+		return nil
+	})
+	if err != nil {
+		return err
+	}
+
+	if err = GitControlSecrets(Snapshot{
+		Command:   fmt.Sprintf("checkpoint restore %s", name),
+		Profile:   profile,
+		SecretIDs: restored,
+	}, secretsPath); err != nil {
+		return err
+	}
+	fmt.Printf("checkpoint %s %s\n", name, color.GreenString("restored"))
+	return nil
+}
+
+// checkpointCommit returns the commit a checkpoint tag points to, annotated tags are peeled
+func checkpointCommit(repo *git.Repository, ref *plumbing.Reference) (*object.Commit, error) {
+	tag, err := repo.TagObject(ref.Hash())
+	if err == nil {
+		return tag.Commit()
+	}
+	if !errors.Is(err, plumbing.ErrObjectNotFound) {
+		return nil, err
+	}
+	return repo.CommitObject(ref.Hash())
+}
@@ -0,0 +1,54 @@
+package helpers
+
+import (
+	"fmt"
+	"os"
+	"strings"
+	"time"
+)
+
+// ciGroup is the name of the open log group, empty when none is open
+var ciGroup string
+
+// CIEnabled reports whether jaws runs in ci mode, from the --ci flag or JAWS_CI=1
+func CIEnabled(flag bool) bool {
+	switch strings.ToLower(os.Getenv("JAWS_CI")) {
+	case "1", "true", "yes":
+		return true
+	}
+	return flag
+}
+
+// StartCIGroup opens a collapsible log group in github actions or gitlab ci, elsewhere it does nothing
+func StartCIGroup(title string) {
+	switch {
+	case os.Getenv("GITHUB_ACTIONS") == "true":
+		fmt.Fprintf(os.Stderr, "::group::%s\n", title)
+	case os.Getenv("GITLAB_CI") == "true":
+		fmt.Fprintf(os.Stderr, "\x1b[0Ksection_start:%d:jaws[collapsed=true]\r\x1b[0K%s\n", time.Now().Unix(), title)
+	default:
+		return
+	}
+	ciGroup = title
+}
+
+// EndCIGroup closes the log group opened by StartCIGroup
+func EndCIGroup() {
+	if ciGroup == "" {
+		return
+	}
+	switch {
+	case os.Getenv("GITHUB_ACTIONS") == "true":
+		fmt.Fprintln(os.Stderr, "::endgroup::")
+	case os.Getenv("GITLAB_CI") == "true":
+		fmt.Fprintf(os.Stderr, "\x1b[0Ksection_end:%d:jaws\r\x1b[0K\n", time.Now().Unix())
+	}
+	ciGroup = ""
+}
+
+// CIError annotates the run with an error in github actions, gitlab ci shows the error printed to stderr
+func CIError(msg string) {
+	if os.Getenv("GITHUB_ACTIONS") == "true" {
+		fmt.Fprintf(os.Stderr, "::error title=jaws::%s\n", strings.ReplaceAll(msg, "\n", "%0A"))
+	}
+}
@@ -0,0 +1,61 @@
+package helpers
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"os/signal"
+	"strings"
+	"syscall"
+)
+
+// RunCompose writes env to a private env file, in memory backed /dev/shm when there is one, runs
+// docker compose --env-file with args and shreds the file once compose exits
+func RunCompose(env []string, args []string) error {
+	dir := os.TempDir()
+	if info, err := os.Stat("/dev/shm"); err == nil && info.IsDir() {
+		dir = "/dev/shm"
+	}
+	f, err := os.CreateTemp(dir, "jaws-compose-*.env")
+	if err != nil {
+		return err
+	}
+	defer shred(f.Name())
+	if err = f.Chmod(SecretFileMode); err != nil {
+		f.Close()
+		return err
+	}
+	if _, err = f.WriteString(strings.Join(env, "\n") + "\n"); err != nil {
+		f.Close()
+		return err
+	}
+	if err = f.Close(); err != nil {
+		return err
+	}
+
+	// compose handles ctrl-c itself, jaws waits for it so the env file is always shredded
+	signals := make(chan os.Signal, 1)
+	signal.Notify(signals, os.Interrupt, syscall.SIGTERM)
+	defer signal.Stop(signals)
+
+	c := exec.Command("docker", append([]string{"compose", "--env-file", f.Name()}, args...)...)
+	c.Stdin = os.Stdin
+	c.Stdout = os.Stdout
+	c.Stderr = os.Stderr
+	if err = c.Run(); err != nil {
+		return fmt.Errorf("docker compose: %w", err)
+	}
+	return nil
+}
+
+// shred overwrites a file with zeros before removing it
+func shred(path string) {
+	if info, err := os.Stat(path); err == nil {
+		if f, err := os.OpenFile(path, os.O_WRONLY, 0); err == nil {
+			f.Write(make([]byte, info.Size()))
+			f.Sync()
+			f.Close()
+		}
+	}
+	os.Remove(path)
+}
@@ -0,0 +1,219 @@
+package helpers
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"regexp"
+	"sort"
+	"strings"
+	"unicode/utf8"
+
+	"github.com/fatih/color"
+	"github.com/go-git/go-git/v5"
+	"github.com/go-git/go-git/v5/plumbing"
+	"github.com/go-git/go-git/v5/plumbing/format/diff"
+	"github.com/go-git/go-git/v5/plumbing/object"
+	utildiff "github.com/go-git/go-git/v5/utils/diff"
+	"github.com/sergi/go-diff/diffmatchpatch"
+)
+
+const maskedValue = "•••"
+
+// maskPattern matches `key: value`, `key = value` and `"key": value` lines so only the value gets masked
+var maskPattern = regexp.MustCompile(`^(\s*"?[\w.\-/]+"?\s*[:=]\s*)\S.*$`)
+
+// DiffFile is the change to a single secret, an empty From or To means the secret was added or removed
+type DiffFile struct {
+	From   string
+	To     string
+	Binary bool
+	Chunks []diff.Chunk
+}
+
+// textChunk is a diff.Chunk built from a diffmatchpatch diff
+type textChunk struct {
+	content string
+	op      diff.Operation
+}
+
+func (c textChunk) Content() string {
+	return c.content
+}
+
+func (c textChunk) Type() diff.Operation {
+	return c.op
+}
+
+// RenderDiff prints the changed lines of every file, added lines in green and removed lines in red
+// with keys in bold, if mask is set values are replaced so only the changed keys can be seen
+func RenderDiff(files []DiffFile, mask bool) {
+	bold := color.New(color.Bold)
+	for _, f := range files {
+		bold.Printf("--- %s\n+++ %s\n", diffPath(f.From, "a"), diffPath(f.To, "b"))
+		if f.Binary {
+			fmt.Println("binary file changed")
+			continue
+		}
+		for _, chunk := range f.Chunks {
+			for _, line := range strings.SplitAfter(chunk.Content(), "\n") {
+				if line == "" {
+					continue
+				}
+				line = strings.TrimSuffix(line, "\n")
+				switch chunk.Type() {
+				case diff.Add:
+					fmt.Println(renderLine("+", line, color.FgGreen, mask))
+				case diff.Delete:
+					fmt.Println(renderLine("-", line, color.FgRed, mask))
+				default:
+					fmt.Println(renderLine(" ", line, color.Reset, mask))
+				}
+			}
+		}
+	}
+}
+
+// renderLine colors a diff line, the key of `key: value` style lines is printed in bold
+func renderLine(prefix string, line string, attr color.Attribute, mask bool) string {
+	if mask {
+		line = maskLine(line)
+	}
+	m := maskPattern.FindStringSubmatch(line)
+	if m == nil {
+		return color.New(attr).Sprint(prefix + line)
+	}
+	key := color.New(attr, color.Bold).Sprint(prefix + m[1])
+	return key + color.New(attr).Sprint(strings.TrimPrefix(line, m[1]))
+}
+
+func diffPath(path string, prefix string) string {
+	if path == "" {
+		return "/dev/null"
+	}
+	return fmt.Sprintf("%s/%s", prefix, path)
+}
+
+// maskLine hides the value of a line while keeping its key visible so changed keys can still be seen
+func maskLine(line string) string {
+	if strings.TrimSpace(line) == "" {
+		return line
+	}
+	if m := maskPattern.FindStringSubmatch(line); m != nil {
+		return m[1] + maskedValue
+	}
+	return maskedValue
+}
+
+// patchFiles converts a go-git patch into the files RenderDiff prints
+func patchFiles(patch *object.Patch) []DiffFile {
+	var files []DiffFile
+	for _, fp := range patch.FilePatches() {
+		from, to := fp.Files()
+		f := DiffFile{
+			Binary: fp.IsBinary(),
+			Chunks: fp.Chunks(),
+		}
+		if from != nil {
+			f.From = from.Path()
+		}
+		if to != nil {
+			f.To = to.Path()
+		}
+		files = append(files, f)
+	}
+	return files
+}
+
+// GitDiff prints the changes in the secrets folder that have not been committed yet, masking values if mask is set
+func GitDiff(secretsPath string, mask bool) error {
+	files, err := WorktreeDiff(secretsPath)
+	if err != nil {
+		return err
+	}
+	RenderDiff(files, mask)
+	return nil
+}
+
+// WorktreeDiff compares the secrets folder against its last commit
+func WorktreeDiff(secretsPath string) ([]DiffFile, error) {
+	repo, err := git.PlainOpen(secretsPath)
+	if err != nil {
+		return nil, fmt.Errorf("opening secrets repo: %w", err)
+	}
+	w, err := repo.Worktree()
+	if err != nil {
+		return nil, err
+	}
+	status, err := w.Status()
+	if err != nil {
+		return nil, err
+	}
+
+	var tree *object.Tree
+	head, err := repo.Head()
+	if err == nil {
+		commit, err := repo.CommitObject(head.Hash())
+		if err != nil {
+			return nil, err
+		}
+		if tree, err = commit.Tree(); err != nil {
+			return nil, err
+		}
+	} else if !errors.Is(err, plumbing.ErrReferenceNotFound) {
+		return nil, err
+	}
+
+	var paths []string
+	for path, fs := range status {
+		if fs.Staging != git.Unmodified || fs.Worktree != git.Unmodified {
+			paths = append(paths, path)
+		}
+	}
+	sort.Strings(paths)
+
+	var files []DiffFile
+	for _, path := range paths {
+		var f DiffFile
+		var from, to string
+		if tree != nil {
+			if file, err := tree.File(path); err == nil {
+				if from, err = file.Contents(); err != nil {
+					return nil, err
+				}
+				f.From = path
+			}
+		}
+		content, err := os.ReadFile(filepath.Join(secretsPath, path))
+		if err == nil {
+			to = string(content)
+			f.To = path
+		} else if !os.IsNotExist(err) {
+			return nil, err
+		}
+		if from == to && f.From != "" && f.To != "" {
+			continue
+		}
+		if !utf8.ValidString(from) || !utf8.ValidString(to) {
+			f.Binary = true
+		} else {
+			for _, d := range utildiff.Do(from, to) {
+				f.Chunks = append(f.Chunks, textChunk{content: d.Text, op: diffOperation(d.Type)})
+			}
+		}
+		files = append(files, f)
+	}
+	return files, nil
+}
+
+func diffOperation(op diffmatchpatch.Operation) diff.Operation {
+	switch op {
+	case diffmatchpatch.DiffInsert:
+		return diff.Add
+	case diffmatchpatch.DiffDelete:
+		return diff.Delete
+	default:
+		return diff.Equal
+	}
+}
@@ -0,0 +1,121 @@
+package helpers
+
+import (
+	"fmt"
+	"time"
+
+	"github.com/fatih/color"
+	"github.com/go-git/go-git/v5"
+	"github.com/go-git/go-git/v5/plumbing"
+	"github.com/go-git/go-git/v5/plumbing/object"
+)
+
+// GitGC squashes the commits in the secrets folder older than keepDays into a single root commit,
+// then prunes the objects that are no longer reachable and repacks the rest
+func GitGC(secretsPath string, keepDays int) error {
+	repo, err := git.PlainOpen(secretsPath)
+	if err != nil {
+		return fmt.Errorf("opening secrets repo: %w", err)
+	}
+	head, err := repo.Head()
+	if err != nil {
+		return err
+	}
+	cutoff := time.Now().AddDate(0, 0, -keepDays)
+
+	// walk the first parent history, newest first
+	var commits []*object.Commit
+	c, err := repo.CommitObject(head.Hash())
+	if err != nil {
+		return err
+	}
+	for {
+		commits = append(commits, c)
+		if c.NumParents() == 0 {
+			break
+		}
+		if c, err = c.Parent(0); err != nil {
+			return err
+		}
+	}
+
+	// split is the newest commit older than the cutoff, it and everything before it get squashed
+	split := -1
+	for i, c := range commits {
+		if c.Committer.When.Before(cutoff) {
+			split = i
+			break
+		}
+	}
+	squashed := len(commits) - split
+	if split == -1 || squashed < 2 {
+		fmt.Printf("no commits older than %d days to squash\n", keepDays)
+	} else {
+		rewritten := map[plumbing.Hash]plumbing.Hash{}
+		parent, err := writeCommit(repo, &object.Commit{
+			Author:    commits[split].Author,
+			Committer: commits[split].Committer,
+			Message:   fmt.Sprintf("jaws vcs gc: squashed %d commit(s) older than %s\n", squashed, cutoff.Format("2006-01-02")),
+			TreeHash:  commits[split].TreeHash,
+		})
+		if err != nil {
+			return err
+		}
+		rewritten[commits[split].Hash] = parent
+		for i := split - 1; i >= 0; i-- {
+			parent, err = writeCommit(repo, &object.Commit{
+				Author:       commits[i].Author,
+				Committer:    commits[i].Committer,
+				Message:      commits[i].Message,
+				TreeHash:     commits[i].TreeHash,
+				ParentHashes: []plumbing.Hash{parent},
+			})
+			if err != nil {
+				return err
+			}
+			rewritten[commits[i].Hash] = parent
+		}
+		if err = repo.Storer.SetReference(plumbing.NewHashReference(head.Name(), parent)); err != nil {
+			return err
+		}
+
+		// move checkpoints onto the rewritten commits
+		tags, err := repo.Tags()
+		if err != nil {
+			return err
+		}
+		err = tags.ForEach(func(ref *plumbing.Reference) error {
+			if hash, ok := rewritten[ref.Hash()]; ok {
+				return repo.Storer.SetReference(plumbing.NewHashReference(ref.Name(), hash))
+			}
+			color.Yellow("checkpoint %s points into the squashed history and keeps it from being pruned", ref.Name().Short())
+			return nil
+		})
+		if err != nil {
+			return err
+		}
+		fmt.Printf("%d commit(s) %s\n", squashed, color.YellowString("squashed"))
+	}
+
+	err = repo.Prune(git.PruneOptions{
+		OnlyObjectsOlderThan: cutoff,
+		Handler:              repo.DeleteObject,
+	})
+	if err != nil {
+		return err
+	}
+	if err = repo.RepackObjects(&git.RepackConfig{}); err != nil {
+		return err
+	}
+	fmt.Printf("%s %s\n", secretsPath, color.GreenString("garbage collected"))
+	return nil
+}
+
+// writeCommit stores a new commit object and returns its hash
+func writeCommit(repo *git.Repository, c *object.Commit) (plumbing.Hash, error) {
+	obj := repo.Storer.NewEncodedObject()
+	if err := c.Encode(obj); err != nil {
+		return plumbing.ZeroHash, err
+	}
+	return repo.Storer.SetEncodedObject(obj)
+}
@@ -1,12 +1,24 @@
 package helpers
 
 import (
+	"errors"
+	"fmt"
 	"os"
 	"os/exec"
+	"path/filepath"
+	"sort"
+	"strings"
+	"time"
+
+	"github.com/fatih/color"
+	"github.com/go-git/go-git/v5"
+	"github.com/go-git/go-git/v5/config"
+	"github.com/go-git/go-git/v5/plumbing"
+	"github.com/go-git/go-git/v5/plumbing/object"
 )
 
-func GitDiff(secretsPath string) error {
-	c := exec.Command("git", "diff")
+func GitStatus(secretsPath string) error {
+	c := exec.Command("git", "status")
 	c.Dir = secretsPath
 	c.Stderr = os.Stderr
 	c.Stdout = os.Stdout
@@ -14,15 +26,395 @@ func GitDiff(secretsPath string) error {
 	return nil
 }
 
-func GitStatus(secretsPath string) error {
-	c := exec.Command("git", "status")
-	c.Dir = secretsPath
-	c.Stderr = os.Stderr
-	c.Stdout = os.Stdout
-	c.Run()
+// StatusEntry is a file in the secrets folder with uncommitted changes
+type StatusEntry struct {
+	Path     string `json:"path" yaml:"path"`
+	Staging  string `json:"staging" yaml:"staging"`
+	Worktree string `json:"worktree" yaml:"worktree"`
+}
+
+// GitStatusEntries returns the files in the secrets folder with uncommitted changes, sorted by path
+func GitStatusEntries(secretsPath string) ([]StatusEntry, error) {
+	repo, err := git.PlainOpen(secretsPath)
+	if err != nil {
+		return nil, fmt.Errorf("opening secrets repo: %w", err)
+	}
+	w, err := repo.Worktree()
+	if err != nil {
+		return nil, err
+	}
+	status, err := w.Status()
+	if err != nil {
+		return nil, err
+	}
+	entries := []StatusEntry{}
+	for path, fs := range status {
+		if fs.Staging == git.Unmodified && fs.Worktree == git.Unmodified {
+			continue
+		}
+		entries = append(entries, StatusEntry{
+			Path:     path,
+			Staging:  statusName(fs.Staging),
+			Worktree: statusName(fs.Worktree),
+		})
+	}
+	sort.Slice(entries, func(i, j int) bool {
+		return entries[i].Path < entries[j].Path
+	})
+	return entries, nil
+}
+
+// StatusTable lays out status entries as rows of path, staging and worktree status
+func StatusTable(entries []StatusEntry) Table {
+	t := Table{Header: []string{"path", "staging", "worktree"}}
+	for _, e := range entries {
+		t.AddRow(e.Path, e.Staging, e.Worktree)
+	}
+	return t
+}
+
+// statusName spells out a go-git status code
+func statusName(code git.StatusCode) string {
+	switch code {
+	case git.Unmodified:
+		return "unmodified"
+	case git.Untracked:
+		return "untracked"
+	case git.Modified:
+		return "modified"
+	case git.Added:
+		return "added"
+	case git.Deleted:
+		return "deleted"
+	case git.Renamed:
+		return "renamed"
+	case git.Copied:
+		return "copied"
+	case git.UpdatedButUnmerged:
+		return "unmerged"
+	default:
+		return string(code)
+	}
+}
+
+// GitPush pushes the branches and tags of the secrets folder to remoteURL
+func GitPush(secretsPath string, remoteURL string, profile string) error {
+	if remoteURL == "" {
+		return errors.New("no vcs_remote set in the general block of the config")
+	}
+	repo, err := git.PlainOpen(secretsPath)
+	if err != nil {
+		return fmt.Errorf("opening secrets repo: %w", err)
+	}
+
+	remote, err := repo.Remote(git.DefaultRemoteName)
+	if err == nil && remote.Config().URLs[0] != remoteURL {
+		if err = repo.DeleteRemote(git.DefaultRemoteName); err != nil {
+			return err
+		}
+		err = git.ErrRemoteNotFound
+	}
+	if errors.Is(err, git.ErrRemoteNotFound) {
+		remote, err = repo.CreateRemote(&config.RemoteConfig{
+			Name: git.DefaultRemoteName,
+			URLs: []string{remoteURL},
+		})
+	}
+	if err != nil {
+		return err
+	}
+
+	err = remote.Push(&git.PushOptions{
+		RefSpecs: []config.RefSpec{
+			"refs/heads/*:refs/heads/*",
+			"refs/tags/*:refs/tags/*",
+		},
+	})
+	if isDiverged(err) {
+		return resolveDivergedPush(repo, remote, remoteURL, profile)
+	}
+	if errors.Is(err, git.NoErrAlreadyUpToDate) {
+		fmt.Printf("%s %s\n", remoteURL, color.CyanString("already up to date"))
+		return nil
+	}
+	if err != nil {
+		return err
+	}
+	fmt.Printf("%s %s\n", remoteURL, color.GreenString("pushed"))
 	return nil
 }
 
-func NewGitDiff(secretsPath string) error {
+// isDiverged reports whether a push was rejected because the remote has commits the secrets folder does not
+func isDiverged(err error) bool {
+	return err != nil && (errors.Is(err, git.ErrForceNeeded) || strings.Contains(err.Error(), "non-fast-forward"))
+}
+
+// resolveDivergedPush fetches the remote history and asks whether the local or the remote history should win
+// or both should be merged in the editor, a replaced local history is kept under a tag so it can still be
+// restored with jaws checkpoint restore
+func resolveDivergedPush(repo *git.Repository, remote *git.Remote, remoteURL string, profile string) error {
+	err := remote.Fetch(&git.FetchOptions{
+		RefSpecs: []config.RefSpec{"+refs/heads/*:refs/remotes/origin/*"},
+	})
+	if err != nil && !errors.Is(err, git.NoErrAlreadyUpToDate) {
+		return err
+	}
+	head, err := repo.Head()
+	if err != nil {
+		return err
+	}
+	remoteRef, err := repo.Reference(plumbing.NewRemoteReferenceName(git.DefaultRemoteName, head.Name().Short()), true)
+	if err != nil {
+		return err
+	}
+	localCommit, err := repo.CommitObject(head.Hash())
+	if err != nil {
+		return err
+	}
+	remoteCommit, err := repo.CommitObject(remoteRef.Hash())
+	if err != nil {
+		return err
+	}
+
+	if err = RequireInteractive("resolving the diverged history", "run jaws vcs push from a terminal"); err != nil {
+		return err
+	}
+	fmt.Fprintln(os.Stderr, color.YellowString("the history in %s has diverged from the secrets folder", remoteURL))
+	fmt.Fprintf(os.Stderr, "local:  %s\n", commitLine(localCommit))
+	fmt.Fprintf(os.Stderr, "remote: %s\n", commitLine(remoteCommit))
+	for {
+		userResponse, err := Prompt("keep [l]ocal and overwrite the remote, keep [r]emote and replace the secrets folder, [m]erge in the editor, show the [d]iff or [a]bort? [l/r/m/d/A] ")
+		if err != nil {
+			return err
+		}
+
+		switch strings.ToLower(strings.TrimSpace(userResponse)) {
+		case "d", "diff":
+			changes, err := divergedChanges(localCommit, remoteCommit)
+			if err != nil {
+				return err
+			}
+			patch, err := changes.Patch()
+			if err != nil {
+				return err
+			}
+			RenderDiff(patchFiles(patch), false)
+		case "m", "merge":
+			return mergeDiverged(repo, remote, remoteURL, profile, localCommit, remoteCommit)
+		default:
+			return keepDiverged(repo, remote, remoteURL, userResponse, remoteRef.Hash())
+		}
+	}
+}
+
+// keepDiverged overwrites the remote with the local history or the secrets folder with the remote history
+func keepDiverged(repo *git.Repository, remote *git.Remote, remoteURL string, userResponse string, remoteHash plumbing.Hash) error {
+	head, err := repo.Head()
+	if err != nil {
+		return err
+	}
+	switch strings.ToLower(strings.TrimSpace(userResponse)) {
+	case "l", "local":
+		err = remote.Push(&git.PushOptions{
+			RefSpecs: []config.RefSpec{
+				"+refs/heads/*:refs/heads/*",
+				"refs/tags/*:refs/tags/*",
+			},
+		})
+		if err != nil {
+			return err
+		}
+		fmt.Printf("%s %s\n", remoteURL, color.YellowString("overwritten"))
+		return nil
+	case "r", "remote":
+		w, err := repo.Worktree()
+		if err != nil {
+			return err
+		}
+		if err = requireClean(w, "replacing it with the remote"); err != nil {
+			return err
+		}
+		backup := fmt.Sprintf("jaws-local-%s", time.Now().Format("20060102-150405"))
+		if _, err = repo.CreateTag(backup, head.Hash(), nil); err != nil {
+			return err
+		}
+		if err = w.Reset(&git.ResetOptions{Commit: remoteHash, Mode: git.HardReset}); err != nil {
+			return err
+		}
+		fmt.Printf("secrets folder %s, the local commits are kept as checkpoint %s\n", color.YellowString("replaced with the remote"), backup)
+		return nil
+	default:
+		return errors.New("push aborted, the remote history has diverged")
+	}
+}
+
+// requireClean refuses to go on when the secrets folder has uncommitted changes, the reset that follows
+// would lose them
+func requireClean(w *git.Worktree, action string) error {
+	status, err := w.Status()
+	if err != nil {
+		return err
+	}
+	if !status.IsClean() {
+		return fmt.Errorf("the secrets folder has uncommitted changes, push them with jaws set or remove them before %s", action)
+	}
+	return nil
+}
+
+// divergedChanges returns the changes from the remote commit to the local one
+func divergedChanges(localCommit *object.Commit, remoteCommit *object.Commit) (object.Changes, error) {
+	localTree, err := localCommit.Tree()
+	if err != nil {
+		return nil, err
+	}
+	remoteTree, err := remoteCommit.Tree()
+	if err != nil {
+		return nil, err
+	}
+	return object.DiffTree(remoteTree, localTree)
+}
+
+// mergeDiverged resets the secrets folder to the remote history and writes every secret that differs with
+// conflict markers, once they are resolved in the editor the result is committed on top of the remote and pushed
+func mergeDiverged(repo *git.Repository, remote *git.Remote, remoteURL string, profile string, localCommit *object.Commit, remoteCommit *object.Commit) error {
+	changes, err := divergedChanges(localCommit, remoteCommit)
+	if err != nil {
+		return err
+	}
+	w, err := repo.Worktree()
+	if err != nil {
+		return err
+	}
+	if err = requireClean(w, "merging"); err != nil {
+		return err
+	}
+	backup := fmt.Sprintf("jaws-local-%s", time.Now().Format("20060102-150405"))
+	if _, err = repo.CreateTag(backup, localCommit.Hash, nil); err != nil {
+		return err
+	}
+	if err = w.Reset(&git.ResetOptions{Commit: remoteCommit.Hash, Mode: git.HardReset}); err != nil {
+		return err
+	}
+	secretsPath := w.Filesystem.Root()
+
+	var conflicted []string
+	for _, change := range changes {
+		remoteFile, localFile, err := change.Files()
+		if err != nil {
+			return err
+		}
+		var localContent, remoteContent string
+		if localFile != nil {
+			if localContent, err = localFile.Contents(); err != nil {
+				return err
+			}
+		}
+		if remoteFile != nil {
+			if remoteContent, err = remoteFile.Contents(); err != nil {
+				return err
+			}
+		}
+		name := change.To.Name
+		if name == "" {
+			name = change.From.Name
+		}
+		path := filepath.Join(secretsPath, name)
+		merged := fmt.Sprintf("%s local\n%s%s\n%s%s remote\n", conflictStart, withNewline(localContent), conflictSeparator, withNewline(remoteContent), conflictEnd)
+		if err = WriteSecretFile(path, []byte(merged)); err != nil {
+			return err
+		}
+		conflicted = append(conflicted, name)
+	}
+
+	if err = OpenEditor(conflicted, secretsPath); err != nil {
+		return err
+	}
+	for _, name := range conflicted {
+		content, err := os.ReadFile(filepath.Join(secretsPath, name))
+		if err != nil {
+			return err
+		}
+		if strings.Contains(string(content), conflictStart) || strings.Contains(string(content), conflictEnd) {
+			return fmt.Errorf("%s still has conflict markers, resolve them and run jaws set, the local history is kept as checkpoint %s", name, backup)
+		}
+	}
+	if err = GitControlSecrets(Snapshot{
+		Command:   "vcs push merge",
+		Profile:   profile,
+		SecretIDs: conflicted,
+	}, secretsPath); err != nil {
+		return err
+	}
+	err = remote.Push(&git.PushOptions{
+		RefSpecs: []config.RefSpec{
+			"refs/heads/*:refs/heads/*",
+			"refs/tags/*:refs/tags/*",
+		},
+	})
+	if err != nil {
+		return err
+	}
+	fmt.Printf("%s %s, the local history is kept as checkpoint %s\n", remoteURL, color.GreenString("merged and pushed"), backup)
+	return nil
+}
+
+// Conflict markers written around the local and remote versions of a secret by mergeDiverged
+const (
+	conflictStart     = "<<<<<<<"
+	conflictSeparator = "======="
+	conflictEnd       = ">>>>>>>"
+)
+
+func withNewline(s string) string {
+	if s == "" || strings.HasSuffix(s, "\n") {
+		return s
+	}
+	return s + "\n"
+}
+
+// GitExport copies the full history of the secrets folder into a new bare git repository at path
+func GitExport(secretsPath string, path string) error {
+	src, err := git.PlainOpen(secretsPath)
+	if err != nil {
+		return fmt.Errorf("opening secrets repo: %w", err)
+	}
+	dst, err := git.PlainInit(path, true)
+	if err != nil {
+		return fmt.Errorf("creating %s: %w", path, err)
+	}
+
+	objects, err := src.Storer.IterEncodedObjects(plumbing.AnyObject)
+	if err != nil {
+		return err
+	}
+	err = objects.ForEach(func(obj plumbing.EncodedObject) error {
+		_, err := dst.Storer.SetEncodedObject(obj)
+		return err
+	})
+	if err != nil {
+		return err
+	}
+
+	refs, err := src.References()
+	if err != nil {
+		return err
+	}
+	err = refs.ForEach(func(ref *plumbing.Reference) error {
+		if !ref.Name().IsBranch() && !ref.Name().IsTag() {
+			return nil
+		}
+		return dst.Storer.SetReference(ref)
+	})
+	if err != nil {
+		return err
+	}
+	head, err := src.Storer.Reference(plumbing.HEAD)
+	if err != nil {
+		return err
+	}
+	if err = dst.Storer.SetReference(head); err != nil {
+		return err
+	}
+	fmt.Printf("%s %s\n", path, color.GreenString("exported"))
 	return nil
 }
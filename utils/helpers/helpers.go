@@ -1,13 +1,17 @@
 package helpers
 
 import (
+	"errors"
 	"fmt"
 	"os"
 	"os/exec"
+	"path/filepath"
+	"strings"
 	"time"
 
 	"github.com/fatih/color"
 	"github.com/go-git/go-git/v5"
+	"github.com/go-git/go-git/v5/plumbing/format/index"
 	"github.com/go-git/go-git/v5/plumbing/object"
 )
 
@@ -17,18 +21,26 @@ func OpenEditor(secretsIDs []string, secretsPath string) error {
 		return fmt.Errorf("no secrets selected")
 	}
 	for _, id := range secretsIDs {
-		secretsList = append(secretsList, fmt.Sprintf("%s/%s", secretsPath, id))
+		secretsList = append(secretsList, filepath.Join(secretsPath, id))
+	}
+	if err := RequireInteractive("opening an editor", "drop --editor"); err != nil {
+		return err
 	}
 	editor, present := os.LookupEnv("EDITOR")
 	if !present {
-		fmt.Printf("set EDITOR environment varible in order to not see this again\n")
-		fmt.Printf("Enter editor: ")
-		var newEditor string
-		fmt.Scanln(&newEditor)
+		fmt.Fprintf(os.Stderr, "set EDITOR environment varible in order to not see this again\n")
+		newEditor, err := Prompt("Enter editor: ")
+		if err != nil {
+			return err
+		}
 		editor = newEditor
 	}
 
-	editCmd := exec.Command(editor, secretsList...)
+	editorArgs := editorCommand(editor)
+	if len(editorArgs) == 0 {
+		return fmt.Errorf("no editor set")
+	}
+	editCmd := exec.Command(editorArgs[0], append(editorArgs[1:], secretsList...)...)
 	editCmd.Stdin = os.Stdin
 	editCmd.Stdout = os.Stdout
 	editCmd.Stderr = os.Stderr
@@ -38,6 +50,47 @@ func OpenEditor(secretsIDs []string, secretsPath string) error {
 	return nil
 }
 
+// editorCommand splits EDITOR into the program and its arguments. EDITOR may carry arguments, e.g.
+// `code --wait`, or be a path with spaces, e.g. C:\Program Files\Notepad++\notepad++.exe, which is
+// used as is when it names a program, otherwise quoted words stay together
+func editorCommand(editor string) []string {
+	editor = strings.TrimSpace(editor)
+	if editor == "" {
+		return nil
+	}
+	if _, err := exec.LookPath(editor); err == nil {
+		return []string{editor}
+	}
+	var args []string
+	var word strings.Builder
+	var quote rune
+	inWord := false
+	for _, r := range editor {
+		switch {
+		case quote != 0 && r == quote:
+			quote = 0
+		case quote != 0:
+			word.WriteRune(r)
+		case r == '"' || r == '\'':
+			quote = r
+			inWord = true
+		case r == ' ' || r == '\t':
+			if inWord {
+				args = append(args, word.String())
+				word.Reset()
+				inWord = false
+			}
+		default:
+			word.WriteRune(r)
+			inWord = true
+		}
+	}
+	if inWord {
+		args = append(args, word.String())
+	}
+	return args
+}
+
 func CheckIfGitRepo(path string, shouldWarn bool) bool {
 	_, err := os.Stat(fmt.Sprintf("%s/.git", path))
 	if os.IsNotExist(err) {
@@ -56,7 +109,47 @@ func gitRepoWarning() {
 	color.Cyan("recommend putting 'secrets' into your .gitignore file")
 }
 
-func GitControlSecrets(secretIDs []string, secretsPath string) error {
+// Snapshot describes what jaws recorded when committing secrets to the secrets folder
+type Snapshot struct {
+	Command   string
+	Profile   string
+	SecretIDs []string
+}
+
+// Message formats the snapshot as a commit message, the details are git trailers so they can be parsed back out
+func (s Snapshot) Message() string {
+	var b strings.Builder
+	fmt.Fprintf(&b, "jaws %s: %d secret(s) from %s\n\n", s.Command, len(s.SecretIDs), s.Profile)
+	fmt.Fprintf(&b, "Jaws-Command: %s\n", s.Command)
+	fmt.Fprintf(&b, "Jaws-Profile: %s\n", s.Profile)
+	fmt.Fprintf(&b, "Jaws-Count: %d\n", len(s.SecretIDs))
+	for _, id := range s.SecretIDs {
+		fmt.Fprintf(&b, "Jaws-Secret: %s\n", id)
+	}
+	return b.String()
+}
+
+// ParseSnapshot reads the jaws trailers back out of a commit message
+func ParseSnapshot(message string) Snapshot {
+	var s Snapshot
+	for _, line := range strings.Split(message, "\n") {
+		key, value, found := strings.Cut(line, ": ")
+		if !found {
+			continue
+		}
+		switch key {
+		case "Jaws-Command":
+			s.Command = value
+		case "Jaws-Profile":
+			s.Profile = value
+		case "Jaws-Secret":
+			s.SecretIDs = append(s.SecretIDs, value)
+		}
+	}
+	return s
+}
+
+func GitControlSecrets(snapshot Snapshot, secretsPath string) error {
 	isRepo := CheckIfGitRepo(secretsPath, false)
 	var repo *git.Repository
 	var err error
@@ -76,19 +169,40 @@ func GitControlSecrets(secretIDs []string, secretsPath string) error {
 		return err
 	}
 
-	l := len(secretIDs)
 	var addOptions *git.AddOptions
-
-	for i := 0; i < l-1; i++ {
+	for _, id := range snapshot.SecretIDs {
+		// a removed secret is staged as a deletion, one that was never committed has nothing to remove
+		if _, statErr := os.Stat(filepath.Join(secretsPath, id)); os.IsNotExist(statErr) {
+			if _, err = w.Remove(id); err != nil && !errors.Is(err, index.ErrEntryNotFound) {
+				return err
+			}
+			continue
+		}
 		addOptions = &git.AddOptions{
 			All:  false,
-			Path: secretIDs[i],
+			Path: id,
 		}
 		if err = w.AddWithOptions(addOptions); err != nil {
 			return err
 		}
 	}
 
+	// nothing differs from the last commit, e.g. a secret downloaded again without changes
+	status, err := w.Status()
+	if err != nil {
+		return err
+	}
+	staged := false
+	for _, s := range status {
+		if s.Staging != git.Unmodified && s.Staging != git.Untracked {
+			staged = true
+			break
+		}
+	}
+	if !staged {
+		return nil
+	}
+
 	commitOptions := &git.CommitOptions{
 		All: false,
 		Author: &object.Signature{
@@ -97,7 +211,7 @@ func GitControlSecrets(secretIDs []string, secretsPath string) error {
 			When:  time.Now(),
 		},
 	}
-	_, err = w.Commit(fmt.Sprintf("firm commit %v", time.Now()), commitOptions)
+	_, err = w.Commit(snapshot.Message(), commitOptions)
 	if err != nil {
 		return err
 	}
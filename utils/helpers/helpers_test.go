@@ -0,0 +1,27 @@
+package helpers
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestParseSnapshot(t *testing.T) {
+	tests := []Snapshot{
+		{Command: "set", Profile: "default", SecretIDs: []string{"prod/app/config"}},
+		{Command: "vcs push merge", Profile: "prod", SecretIDs: []string{"a", "b/c", "d: e"}},
+		{Command: "checkpoint restore before-rotation", Profile: "default", SecretIDs: nil},
+	}
+	for _, want := range tests {
+		if got := ParseSnapshot(want.Message()); !reflect.DeepEqual(got, want) {
+			t.Errorf("ParseSnapshot(%q) = %+v, want %+v", want.Message(), got, want)
+		}
+	}
+}
+
+func TestParseSnapshotIgnoresOtherLines(t *testing.T) {
+	message := "update secrets by hand\n\nSigned-off-by: someone\nJaws-Secret: prod/app/config\n"
+	want := Snapshot{SecretIDs: []string{"prod/app/config"}}
+	if got := ParseSnapshot(message); !reflect.DeepEqual(got, want) {
+		t.Errorf("ParseSnapshot(%q) = %+v, want %+v", message, got, want)
+	}
+}
@@ -0,0 +1,76 @@
+package helpers
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"strings"
+
+	"github.com/mattn/go-isatty"
+)
+
+var nonInteractive bool
+
+// SetNonInteractive turns off every prompt, jaws also never prompts when stdin is not a terminal
+func SetNonInteractive(disable bool) {
+	nonInteractive = disable
+}
+
+// Interactive reports whether jaws may prompt the user, on a terminal or through an askpass program
+func Interactive() bool {
+	if nonInteractive {
+		return false
+	}
+	return terminal() || askpass() != ""
+}
+
+// terminal reports whether stdin is a terminal
+func terminal() bool {
+	fd := os.Stdin.Fd()
+	return isatty.IsTerminal(fd) || isatty.IsCygwinTerminal(fd)
+}
+
+// askpass returns the program that asks prompts in a dialog, JAWS_ASKPASS or else SSH_ASKPASS
+func askpass() string {
+	if program := os.Getenv("JAWS_ASKPASS"); program != "" {
+		return program
+	}
+	return os.Getenv("SSH_ASKPASS")
+}
+
+// Prompt asks question and returns the trimmed answer, on the terminal or, without one or when
+// SSH_ASKPASS_REQUIRE is force or prefer, through the askpass program which gets question as its argument
+func Prompt(question string) (string, error) {
+	program := askpass()
+	require := os.Getenv("SSH_ASKPASS_REQUIRE")
+	if program != "" && (!terminal() || require == "force" || require == "prefer") {
+		out, err := exec.Command(program, question).Output()
+		if err != nil {
+			return "", fmt.Errorf("asking with %s: %w", program, err)
+		}
+		return strings.TrimSpace(string(out)), nil
+	}
+	fmt.Fprint(os.Stderr, question)
+	var answer string
+	fmt.Scanln(&answer)
+	return strings.TrimSpace(answer), nil
+}
+
+// PromptError is returned when a prompt is needed but jaws runs non-interactively
+type PromptError struct {
+	Prompt string
+	Hint   string
+}
+
+func (e *PromptError) Error() string {
+	return fmt.Sprintf("%s needs a prompt but jaws is running non-interactively, %s", e.Prompt, e.Hint)
+}
+
+// RequireInteractive fails fast when a prompt is needed but jaws runs non-interactively,
+// hint tells the user how to provide the answer up front
+func RequireInteractive(prompt string, hint string) error {
+	if Interactive() {
+		return nil
+	}
+	return &PromptError{Prompt: prompt, Hint: hint}
+}
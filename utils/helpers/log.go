@@ -0,0 +1,216 @@
+package helpers
+
+import (
+	"fmt"
+	"strings"
+	"time"
+
+	"github.com/fatih/color"
+	"github.com/go-git/go-git/v5"
+	"github.com/go-git/go-git/v5/plumbing"
+	"github.com/go-git/go-git/v5/plumbing/format/diff"
+	"github.com/go-git/go-git/v5/plumbing/object"
+	"github.com/go-git/go-git/v5/utils/merkletrie"
+)
+
+// LogEntry is a commit made in the secrets folder
+type LogEntry struct {
+	Hash    string       `json:"hash" yaml:"hash"`
+	Date    time.Time    `json:"date" yaml:"date"`
+	Summary string       `json:"summary" yaml:"summary"`
+	Command string       `json:"command,omitempty" yaml:"command,omitempty"`
+	Profile string       `json:"profile,omitempty" yaml:"profile,omitempty"`
+	Secrets []string     `json:"secrets,omitempty" yaml:"secrets,omitempty"`
+	Stats   *ChangeStats `json:"stats,omitempty" yaml:"stats,omitempty"`
+}
+
+// newLogEntry reads the summary and jaws snapshot trailers of a commit
+func newLogEntry(c *object.Commit) LogEntry {
+	snapshot := ParseSnapshot(c.Message)
+	return LogEntry{
+		Hash:    c.Hash.String(),
+		Date:    c.Author.When,
+		Summary: strings.SplitN(c.Message, "\n", 2)[0],
+		Command: snapshot.Command,
+		Profile: snapshot.Profile,
+		Secrets: snapshot.SecretIDs,
+	}
+}
+
+// GitLogEntries returns the commits made in the secrets folder with their change stats, newest first
+func GitLogEntries(secretsPath string) ([]LogEntry, error) {
+	repo, err := git.PlainOpen(secretsPath)
+	if err != nil {
+		return nil, fmt.Errorf("opening secrets repo: %w", err)
+	}
+	iter, err := repo.Log(&git.LogOptions{})
+	if err != nil {
+		return nil, err
+	}
+	var entries []LogEntry
+	err = iter.ForEach(func(c *object.Commit) error {
+		stats, err := commitStats(c)
+		if err != nil {
+			return err
+		}
+		entry := newLogEntry(c)
+		entry.Stats = &stats
+		entries = append(entries, entry)
+		return nil
+	})
+	return entries, err
+}
+
+// LogTable lays out log entries as rows of short hash, date and summary, plus change stats when the entries have them
+func LogTable(entries []LogEntry) Table {
+	t := Table{Header: []string{"commit", "date", "summary"}}
+	withStats := len(entries) != 0 && entries[0].Stats != nil
+	if withStats {
+		t.Header = append(t.Header, "changes", "bytes")
+	}
+	for _, e := range entries {
+		row := []string{e.Hash[:8], e.Date.Format("2006-01-02 15:04:05"), e.Summary}
+		if withStats && e.Stats != nil {
+			row = append(row, e.Stats.String(), fmt.Sprint(e.Stats.Bytes))
+		}
+		t.AddRow(row...)
+	}
+	return t
+}
+
+// String formats the short hash, date and summary line of the entry
+func (e LogEntry) String() string {
+	return fmt.Sprintf("%s %s %s",
+		color.YellowString(e.Hash[:8]),
+		e.Date.Format("2006-01-02 15:04:05"),
+		e.Summary,
+	)
+}
+
+// ChangeStats counts the files a commit added, modified and removed and the bytes of changed lines
+type ChangeStats struct {
+	Added    int `json:"added" yaml:"added"`
+	Modified int `json:"modified" yaml:"modified"`
+	Removed  int `json:"removed" yaml:"removed"`
+	Bytes    int `json:"bytes" yaml:"bytes"`
+}
+
+func (s ChangeStats) String() string {
+	return fmt.Sprintf("+%d ~%d -%d", s.Added, s.Modified, s.Removed)
+}
+
+// commitStats computes the change stats of a commit against its first parent
+func commitStats(c *object.Commit) (ChangeStats, error) {
+	var stats ChangeStats
+	changes, err := commitChanges(c)
+	if err != nil {
+		return stats, err
+	}
+	for _, change := range changes {
+		action, err := change.Action()
+		if err != nil {
+			return stats, err
+		}
+		switch action {
+		case merkletrie.Insert:
+			stats.Added++
+		case merkletrie.Modify:
+			stats.Modified++
+		case merkletrie.Delete:
+			stats.Removed++
+		}
+	}
+	patch, err := changes.Patch()
+	if err != nil {
+		return stats, err
+	}
+	for _, fp := range patch.FilePatches() {
+		for _, chunk := range fp.Chunks() {
+			if chunk.Type() != diff.Equal {
+				stats.Bytes += len(chunk.Content())
+			}
+		}
+	}
+	return stats, nil
+}
+
+// GitLogDiff prints the per-file content diff introduced by the commit rev, masking values if mask is set
+func GitLogDiff(secretsPath string, rev string, mask bool) error {
+	repo, err := git.PlainOpen(secretsPath)
+	if err != nil {
+		return fmt.Errorf("opening secrets repo: %w", err)
+	}
+	hash, err := repo.ResolveRevision(plumbing.Revision(rev))
+	if err != nil {
+		return fmt.Errorf("resolving %s: %w", rev, err)
+	}
+	commit, err := repo.CommitObject(*hash)
+	if err != nil {
+		return err
+	}
+	changes, err := commitChanges(commit)
+	if err != nil {
+		return err
+	}
+	patch, err := changes.Patch()
+	if err != nil {
+		return err
+	}
+
+	fmt.Printf("%s %s\n", color.YellowString(commit.Hash.String()[:8]), strings.TrimSpace(commit.Message))
+	RenderDiff(patchFiles(patch), mask)
+	return nil
+}
+
+// printCommit prints the short hash, date and summary line of a commit
+func printCommit(c *object.Commit) {
+	fmt.Println(commitLine(c))
+}
+
+// commitLine formats the short hash, date and summary line of a commit
+func commitLine(c *object.Commit) string {
+	return newLogEntry(c).String()
+}
+
+// commitChanges returns the tree changes between a commit and its first parent
+func commitChanges(c *object.Commit) (object.Changes, error) {
+	tree, err := c.Tree()
+	if err != nil {
+		return nil, err
+	}
+	var parentTree *object.Tree
+	if c.NumParents() > 0 {
+		parent, err := c.Parent(0)
+		if err != nil {
+			return nil, err
+		}
+		if parentTree, err = parent.Tree(); err != nil {
+			return nil, err
+		}
+	}
+	return object.DiffTree(parentTree, tree)
+}
+
+// GitFileHistoryEntries returns every commit in the secrets folder that changed the file of secretID
+func GitFileHistoryEntries(secretsPath string, secretID string) ([]LogEntry, error) {
+	repo, err := git.PlainOpen(secretsPath)
+	if err != nil {
+		return nil, fmt.Errorf("opening secrets repo: %w", err)
+	}
+	iter, err := repo.Log(&git.LogOptions{FileName: &secretID})
+	if err != nil {
+		return nil, err
+	}
+	var entries []LogEntry
+	err = iter.ForEach(func(c *object.Commit) error {
+		entries = append(entries, newLogEntry(c))
+		return nil
+	})
+	if err != nil {
+		return nil, err
+	}
+	if len(entries) == 0 {
+		return nil, fmt.Errorf("no history found for %s in %s", secretID, secretsPath)
+	}
+	return entries, nil
+}
@@ -0,0 +1,80 @@
+package helpers
+
+import (
+	"encoding/csv"
+	"encoding/json"
+	"fmt"
+	"os"
+	"reflect"
+	"strings"
+	"text/tabwriter"
+
+	"gopkg.in/yaml.v3"
+)
+
+// Output formats selected with the global --output flag
+const (
+	OutputTable = "table"
+	OutputJSON  = "json"
+	OutputYAML  = "yaml"
+	OutputCSV   = "csv"
+)
+
+// CheckOutputFormat returns an error for output formats jaws cannot print
+func CheckOutputFormat(format string) error {
+	switch format {
+	case OutputTable, OutputJSON, OutputYAML, OutputCSV:
+		return nil
+	default:
+		return fmt.Errorf("unknown output format `%s`, use table, json, yaml or csv", format)
+	}
+}
+
+// Table is the tabular form of a command's output, printed by the table and csv formats
+type Table struct {
+	Header []string
+	Rows   [][]string
+}
+
+// AddRow appends a row of cells to the table
+func (t *Table) AddRow(cells ...string) {
+	t.Rows = append(t.Rows, cells)
+}
+
+// PrintOutput writes v to stdout as json or yaml, the table and csv formats print t instead,
+// a nil slice is printed as an empty list instead of null
+func PrintOutput(format string, v interface{}, t Table) error {
+	if rv := reflect.ValueOf(v); rv.Kind() == reflect.Slice && rv.IsNil() {
+		v = reflect.MakeSlice(rv.Type(), 0, 0).Interface()
+	}
+	switch format {
+	case OutputJSON:
+		enc := json.NewEncoder(os.Stdout)
+		enc.SetIndent("", "  ")
+		return enc.Encode(v)
+	case OutputYAML:
+		enc := yaml.NewEncoder(os.Stdout)
+		enc.SetIndent(2)
+		if err := enc.Encode(v); err != nil {
+			return err
+		}
+		return enc.Close()
+	case OutputCSV:
+		w := csv.NewWriter(os.Stdout)
+		if len(t.Header) != 0 {
+			if err := w.Write(t.Header); err != nil {
+				return err
+			}
+		}
+		return w.WriteAll(t.Rows)
+	default:
+		w := tabwriter.NewWriter(os.Stdout, 0, 0, 2, ' ', 0)
+		if len(t.Header) != 0 {
+			fmt.Fprintln(w, strings.ToUpper(strings.Join(t.Header, "\t")))
+		}
+		for _, row := range t.Rows {
+			fmt.Fprintln(w, strings.Join(row, "\t"))
+		}
+		return w.Flush()
+	}
+}
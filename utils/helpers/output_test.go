@@ -0,0 +1,55 @@
+package helpers
+
+import (
+	"io"
+	"os"
+	"testing"
+)
+
+// captureStdout returns what f printed to stdout
+func captureStdout(t *testing.T, f func() error) string {
+	t.Helper()
+	r, w, err := os.Pipe()
+	if err != nil {
+		t.Fatal(err)
+	}
+	stdout := os.Stdout
+	os.Stdout = w
+	defer func() { os.Stdout = stdout }()
+	if err := f(); err != nil {
+		t.Fatal(err)
+	}
+	w.Close()
+	out, err := io.ReadAll(r)
+	if err != nil {
+		t.Fatal(err)
+	}
+	return string(out)
+}
+
+func TestPrintOutput(t *testing.T) {
+	type row struct {
+		ID string `json:"id" yaml:"id"`
+	}
+	var nilRows []row
+	tests := []struct {
+		format string
+		v      interface{}
+		want   string
+	}{
+		{OutputJSON, nilRows, "[]\n"},
+		{OutputJSON, []string(nil), "[]\n"},
+		{OutputJSON, []row{{ID: "a"}}, "[\n  {\n    \"id\": \"a\"\n  }\n]\n"},
+		{OutputYAML, nilRows, "[]\n"},
+		{OutputYAML, []row{{ID: "a"}}, "- id: a\n"},
+		{OutputJSON, map[string]int(nil), "null\n"},
+	}
+	for _, tt := range tests {
+		got := captureStdout(t, func() error {
+			return PrintOutput(tt.format, tt.v, Table{})
+		})
+		if got != tt.want {
+			t.Errorf("PrintOutput(%s, %#v) printed %q, want %q", tt.format, tt.v, got, tt.want)
+		}
+	}
+}
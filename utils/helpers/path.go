@@ -4,6 +4,7 @@ import (
 	"fmt"
 	"os"
 	"path/filepath"
+	"strings"
 )
 
 // Path prints directory path to secrets folder and will create the path if it does not exist
@@ -29,7 +30,7 @@ func Path(secretsPath string) error {
 	}
 	_, err = os.Stat(f)
 	if os.IsNotExist(err) {
-		if err = os.MkdirAll(f, 0770); err != nil {
+		if err = os.MkdirAll(f, SecretsDirMode); err != nil {
 			return err
 		}
 	}
@@ -49,3 +50,15 @@ func PathCommand() {
 alias jd=jaws-cd`
 	fmt.Println(shCommand)
 }
+
+// ExpandHome replaces a leading ~ in path with the home directory of the current user
+func ExpandHome(path string) string {
+	if path != "~" && !strings.HasPrefix(path, "~/") && !strings.HasPrefix(path, `~\`) {
+		return path
+	}
+	home, err := os.UserHomeDir()
+	if err != nil {
+		return path
+	}
+	return filepath.Join(home, path[1:])
+}
@@ -0,0 +1,128 @@
+package helpers
+
+import (
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+	"strings"
+)
+
+const (
+	// SecretsDirMode keeps the secrets folder and its sub folders private to the current user
+	SecretsDirMode fs.FileMode = 0700
+	// SecretFileMode keeps secret files private to the current user
+	SecretFileMode fs.FileMode = 0600
+)
+
+// cloudSyncedDirs are path segments of folders that sync their contents to a cloud provider
+var cloudSyncedDirs = []string{"Dropbox", "Library/Mobile Documents", "iCloud Drive", "OneDrive", "Google Drive", "GoogleDrive", "pCloudDrive"}
+
+// WriteSecretFile writes content to path with SecretFileMode, creating missing folders with SecretsDirMode,
+// an existing file is restricted as well
+func WriteSecretFile(path string, content []byte) error {
+	if err := os.MkdirAll(filepath.Dir(path), SecretsDirMode); err != nil {
+		return err
+	}
+	if err := os.WriteFile(path, content, SecretFileMode); err != nil {
+		return err
+	}
+	return restrict(path, false)
+}
+
+// secretMode returns the mode of a secrets folder or a secret file
+func secretMode(dir bool) fs.FileMode {
+	if dir {
+		return SecretsDirMode
+	}
+	return SecretFileMode
+}
+
+// RestrictSecretsDir limits the secrets folder to the current user if it exists
+func RestrictSecretsDir(secretsPath string) error {
+	info, err := os.Stat(secretsPath)
+	if os.IsNotExist(err) {
+		return nil
+	} else if err != nil {
+		return err
+	}
+	ok, err := restricted(secretsPath, info, true)
+	if err != nil || ok {
+		return err
+	}
+	return restrict(secretsPath, true)
+}
+
+// PermissionProblems lists the folders and files in the secrets folder readable by anyone but the current user,
+// the contents of .git are covered by the mode of the .git folder
+func PermissionProblems(secretsPath string) ([]string, error) {
+	var problems []string
+	err := filepath.WalkDir(secretsPath, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		info, err := d.Info()
+		if err != nil {
+			return err
+		}
+		ok, err := restricted(path, info, d.IsDir())
+		if err != nil {
+			return err
+		}
+		if !ok {
+			problems = append(problems, fmt.Sprintf("%s is accessible by other users", path))
+		}
+		if d.IsDir() && d.Name() == ".git" {
+			return filepath.SkipDir
+		}
+		return nil
+	})
+	if os.IsNotExist(err) {
+		return nil, nil
+	}
+	return problems, err
+}
+
+// FixPermissions restricts every folder and file in the secrets folder to the current user and returns how many changed
+func FixPermissions(secretsPath string) (int, error) {
+	fixed := 0
+	err := filepath.WalkDir(secretsPath, func(path string, d fs.DirEntry, err error) error {
+		if err != nil {
+			return err
+		}
+		info, err := d.Info()
+		if err != nil {
+			return err
+		}
+		ok, err := restricted(path, info, d.IsDir())
+		if err != nil {
+			return err
+		}
+		if !ok {
+			if err = restrict(path, d.IsDir()); err != nil {
+				return err
+			}
+			fixed++
+		}
+		if d.IsDir() && d.Name() == ".git" {
+			return filepath.SkipDir
+		}
+		return nil
+	})
+	return fixed, err
+}
+
+// CloudSynced returns the cloud synced folder the secrets folder is inside of, or an empty string
+func CloudSynced(secretsPath string) string {
+	abs, err := filepath.Abs(secretsPath)
+	if err != nil {
+		return ""
+	}
+	abs = filepath.ToSlash(abs)
+	for _, dir := range cloudSyncedDirs {
+		if strings.Contains(abs, "/"+dir+"/") || strings.HasSuffix(abs, "/"+dir) || strings.Contains(abs, "/"+dir+" ") {
+			return dir
+		}
+	}
+	return ""
+}
@@ -0,0 +1,18 @@
+//go:build !windows
+
+package helpers
+
+import (
+	"io/fs"
+	"os"
+)
+
+// restrict limits path to the current user with the mode bits of a secrets folder or secret file
+func restrict(path string, dir bool) error {
+	return os.Chmod(path, secretMode(dir))
+}
+
+// restricted reports whether path is already limited to the current user
+func restricted(path string, info fs.FileInfo, dir bool) (bool, error) {
+	return info.Mode().Perm()&^secretMode(dir) == 0, nil
+}
@@ -0,0 +1,123 @@
+//go:build windows
+
+package helpers
+
+import (
+	"errors"
+	"fmt"
+	"io/fs"
+	"runtime"
+	"unsafe"
+
+	"golang.org/x/sys/windows"
+)
+
+// restrict makes the current user the owner of path and replaces its ACL with a single entry giving that
+// user full control, inherited entries are dropped and folders pass the entry on to new files
+func restrict(path string, dir bool) error {
+	user, err := currentUserSID()
+	if err != nil {
+		return err
+	}
+	sd, err := userOnlyDescriptor(user, dir)
+	if err != nil {
+		return err
+	}
+	dacl, _, err := sd.DACL()
+	if err != nil {
+		return err
+	}
+	return windows.SetNamedSecurityInfo(path, windows.SE_FILE_OBJECT,
+		windows.OWNER_SECURITY_INFORMATION|windows.DACL_SECURITY_INFORMATION|windows.PROTECTED_DACL_SECURITY_INFORMATION,
+		user, nil, dacl, nil)
+}
+
+// restricted reports whether path is owned by the current user behind a protected ACL whose entries all
+// allow that user full control, compared by sid and access mask, the inherit flags of the entries are ignored
+func restricted(path string, info fs.FileInfo, dir bool) (bool, error) {
+	user, err := currentUserSID()
+	if err != nil {
+		return false, err
+	}
+	sd, err := windows.GetNamedSecurityInfo(path, windows.SE_FILE_OBJECT,
+		windows.OWNER_SECURITY_INFORMATION|windows.DACL_SECURITY_INFORMATION)
+	if err != nil {
+		return false, err
+	}
+	owner, _, err := sd.Owner()
+	if err != nil || !owner.Equals(user) {
+		return false, err
+	}
+	control, _, err := sd.Control()
+	if err != nil || control&windows.SE_DACL_PROTECTED == 0 {
+		return false, err
+	}
+	dacl, _, err := sd.DACL()
+	if errors.Is(err, windows.ERROR_OBJECT_NOT_FOUND) || (err == nil && dacl == nil) {
+		// a missing or null DACL lets everyone in
+		return false, nil
+	} else if err != nil {
+		return false, err
+	}
+	header := (*aclHeader)(unsafe.Pointer(dacl))
+	if header.Count == 0 {
+		return false, nil
+	}
+	offset := unsafe.Sizeof(*header)
+	for i := uint16(0); i < header.Count; i++ {
+		ace := (*allowedACE)(unsafe.Add(unsafe.Pointer(dacl), offset))
+		if offset+uintptr(ace.Size) > uintptr(header.Size) {
+			return false, fmt.Errorf("malformed ACL on %s", path)
+		}
+		if ace.Type != accessAllowedACEType || ace.Mask != fileAllAccess {
+			return false, nil
+		}
+		if sid := (*windows.SID)(unsafe.Pointer(&ace.SidStart)); !sid.Equals(user) {
+			return false, nil
+		}
+		offset += uintptr(ace.Size)
+	}
+	runtime.KeepAlive(sd)
+	return true, nil
+}
+
+// aclHeader and allowedACE mirror the ACL and ACCESS_ALLOWED_ACE structs of winnt.h, x/sys has no
+// accessor for the entries of an ACL
+type aclHeader struct {
+	Revision byte
+	Sbz1     byte
+	Size     uint16
+	Count    uint16
+	Sbz2     uint16
+}
+
+type allowedACE struct {
+	Type     byte
+	Flags    byte
+	Size     uint16
+	Mask     uint32
+	SidStart uint32
+}
+
+const (
+	accessAllowedACEType = 0
+	fileAllAccess        = 0x1f01ff
+)
+
+// currentUserSID returns the sid of the user running jaws
+func currentUserSID() (*windows.SID, error) {
+	user, err := windows.GetCurrentProcessToken().GetTokenUser()
+	if err != nil {
+		return nil, fmt.Errorf("looking up current user: %w", err)
+	}
+	return user.User.Sid, nil
+}
+
+// userOnlyDescriptor builds a protected DACL that allows only user
+func userOnlyDescriptor(user *windows.SID, dir bool) (*windows.SECURITY_DESCRIPTOR, error) {
+	inherit := ""
+	if dir {
+		inherit = "OICI"
+	}
+	return windows.SecurityDescriptorFromString(fmt.Sprintf("D:P(A;%s;FA;;;%s)", inherit, user.String()))
+}
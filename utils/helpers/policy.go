@@ -0,0 +1,151 @@
+package helpers
+
+import (
+	"encoding/json"
+	"fmt"
+	"regexp"
+	"time"
+)
+
+// Policy are the rules every mutating command is checked against before it runs
+type Policy struct {
+	ProtectedProfiles []string
+	MinValueLength    int
+	DenyValues        []string
+	MaxRotationAge    time.Duration
+}
+
+// PolicyError is returned when an operation breaks a rule of the policy
+type PolicyError struct {
+	Rule   string
+	Detail string
+}
+
+func (e *PolicyError) Error() string {
+	return fmt.Sprintf("denied by policy %s: %s", e.Rule, e.Detail)
+}
+
+var policy struct {
+	protected  []*regexp.Regexp
+	minLength      int
+	denyValues     []*regexp.Regexp
+	maxRotationAge time.Duration
+}
+
+// SetPolicy compiles and applies p, jaws runs without a policy until it is set
+func SetPolicy(p Policy) error {
+	compile := func(patterns []string) ([]*regexp.Regexp, error) {
+		var compiled []*regexp.Regexp
+		for _, pattern := range patterns {
+			re, err := regexp.Compile(pattern)
+			if err != nil {
+				return nil, fmt.Errorf("policy pattern %s: %w", pattern, err)
+			}
+			compiled = append(compiled, re)
+		}
+		return compiled, nil
+	}
+	protected, err := compile(p.ProtectedProfiles)
+	if err != nil {
+		return err
+	}
+	denyValues, err := compile(p.DenyValues)
+	if err != nil {
+		return err
+	}
+	policy.protected = protected
+	policy.minLength = p.MinValueLength
+	policy.denyValues = denyValues
+	policy.maxRotationAge = p.MaxRotationAge
+	return nil
+}
+
+// CheckProfile denies changes to a protected profile unless they were confirmed with --confirm-prod
+func CheckProfile(profile string, confirmed bool) error {
+	for _, re := range policy.protected {
+		if re.MatchString(profile) && !confirmed {
+			return &PolicyError{
+				Rule:   "protected_profiles",
+				Detail: fmt.Sprintf("profile %s is protected, add --confirm-prod to change it", profile),
+			}
+		}
+	}
+	return nil
+}
+
+// CheckRotation denies pushing over a secret whose managed rotation is overdue, lastRotated is zero
+// for secrets that never rotated
+func CheckRotation(secretID string, lastRotated time.Time) error {
+	if policy.maxRotationAge == 0 || time.Since(lastRotated) <= policy.maxRotationAge {
+		return nil
+	}
+	last := "never"
+	if !lastRotated.IsZero() {
+		last = lastRotated.Format("2006-01-02")
+	}
+	return &PolicyError{
+		Rule:   "max_rotation_age_days",
+		Detail: fmt.Sprintf("%s has managed rotation enabled but last rotated %s, fix its rotation before pushing over it", secretID, last),
+	}
+}
+
+// CheckValue denies weak values, every string field of a json object is checked on its own
+func CheckValue(secretID string, content string) error {
+	if policy.minLength == 0 && len(policy.denyValues) == 0 {
+		return nil
+	}
+	var doc interface{}
+	if err := json.Unmarshal([]byte(content), &doc); err != nil {
+		return checkValue(secretID, content)
+	}
+	return checkJSONValue(secretID, "", doc)
+}
+
+// checkJSONValue walks a json document and checks each string in it, path is the key path to value
+func checkJSONValue(secretID string, path string, value interface{}) error {
+	join := func(key string) string {
+		if path == "" {
+			return key
+		}
+		return path + "." + key
+	}
+	switch v := value.(type) {
+	case string:
+		if path == "" {
+			return checkValue(secretID, v)
+		}
+		return checkValue(secretID+"#"+path, v)
+	case map[string]interface{}:
+		for k, field := range v {
+			if err := checkJSONValue(secretID, join(k), field); err != nil {
+				return err
+			}
+		}
+	case []interface{}:
+		for i, item := range v {
+			if err := checkJSONValue(secretID, join(fmt.Sprint(i)), item); err != nil {
+				return err
+			}
+		}
+	}
+	return nil
+}
+
+// checkValue applies the weak value rules to a single value
+func checkValue(path string, value string) error {
+	if len(value) < policy.minLength {
+		return &PolicyError{
+			Rule:   "min_value_length",
+			Detail: fmt.Sprintf("%s is %d characters, the minimum is %d", path, len(value), policy.minLength),
+		}
+	}
+	for _, re := range policy.denyValues {
+		if re.MatchString(value) {
+			return &PolicyError{
+				Rule:   "deny_values",
+				Detail: fmt.Sprintf("%s matches %s", path, re.String()),
+			}
+		}
+	}
+	return nil
+}
@@ -0,0 +1,56 @@
+package helpers
+
+import (
+	"sort"
+	"strings"
+	"sync"
+)
+
+// minRedactLength keeps short values like `true` or `1` from masking unrelated output
+const minRedactLength = 6
+
+var redactor struct {
+	sync.Mutex
+	values map[string]struct{}
+}
+
+// RegisterSecret remembers a secret value, and the value of each of its key: value lines,
+// so Redact can mask them wherever they show up outside of an explicit print
+func RegisterSecret(content string) {
+	redactor.Lock()
+	defer redactor.Unlock()
+	if redactor.values == nil {
+		redactor.values = map[string]struct{}{}
+	}
+	add := func(v string) {
+		v = strings.TrimSpace(v)
+		if len(v) >= minRedactLength {
+			redactor.values[v] = struct{}{}
+		}
+	}
+	add(content)
+	for _, line := range strings.Split(content, "\n") {
+		if m := maskPattern.FindStringSubmatch(line); m != nil {
+			add(strings.Trim(strings.TrimPrefix(line, m[1]), `"', `))
+			continue
+		}
+		add(line)
+	}
+}
+
+// Redact replaces every registered secret value in s with •••, longest values first
+func Redact(s string) string {
+	redactor.Lock()
+	defer redactor.Unlock()
+	values := make([]string, 0, len(redactor.values))
+	for v := range redactor.values {
+		values = append(values, v)
+	}
+	sort.Slice(values, func(i, j int) bool {
+		return len(values[i]) > len(values[j])
+	})
+	for _, v := range values {
+		s = strings.ReplaceAll(s, v, maskedValue)
+	}
+	return s
+}
@@ -0,0 +1,97 @@
+package helpers
+
+import (
+	"bytes"
+	"encoding/json"
+	"fmt"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/go-git/go-git/v5"
+)
+
+// Validators are the checks run against edited secrets before they are committed and pushed
+type Validators struct {
+	JSON               bool
+	TrailingWhitespace bool
+	MaxSize            int64
+}
+
+// Validate runs the enabled validators against a single secret and returns the first failure
+func (v Validators) Validate(secretsPath string, secretID string) error {
+	content, err := os.ReadFile(filepath.Join(secretsPath, secretID))
+	if err != nil {
+		return err
+	}
+	RegisterSecret(string(content))
+	if v.MaxSize > 0 && int64(len(content)) > v.MaxSize {
+		return fmt.Errorf("%s is %d bytes, more than the max_size of %d", secretID, len(content), v.MaxSize)
+	}
+	if v.TrailingWhitespace {
+		for i, line := range strings.Split(string(content), "\n") {
+			if strings.TrimRight(line, " \t\r") != line {
+				return fmt.Errorf("%s has trailing whitespace on line %d", secretID, i+1)
+			}
+		}
+	}
+	// only secrets that look like json are parsed, plain strings are left alone
+	if v.JSON {
+		trimmed := bytes.TrimSpace(content)
+		if len(trimmed) > 0 && (trimmed[0] == '{' || trimmed[0] == '[') && !json.Valid(trimmed) {
+			return fmt.Errorf("%s is not valid json", secretID)
+		}
+	}
+	return nil
+}
+
+// ValidateAll runs the validators against every secret and reports all failures at once
+func (v Validators) ValidateAll(secretsPath string, secretIDs []string) error {
+	var failures []string
+	for _, id := range secretIDs {
+		if err := v.Validate(secretsPath, id); err != nil {
+			failures = append(failures, err.Error())
+		}
+	}
+	if len(failures) != 0 {
+		return &ValidationError{Failures: failures}
+	}
+	return nil
+}
+
+// ValidationError lists every secret that failed validation
+type ValidationError struct {
+	Failures []string
+}
+
+func (e *ValidationError) Error() string {
+	return fmt.Sprintf("validation failed:\n  %s", strings.Join(e.Failures, "\n  "))
+}
+
+// EditedSecrets returns the secrets that were added or modified since the last commit in the secrets folder
+func EditedSecrets(secretsPath string) ([]string, error) {
+	repo, err := git.PlainOpen(secretsPath)
+	if err != nil {
+		return nil, fmt.Errorf("opening secrets repo: %w", err)
+	}
+	w, err := repo.Worktree()
+	if err != nil {
+		return nil, err
+	}
+	status, err := w.Status()
+	if err != nil {
+		return nil, err
+	}
+	var edited []string
+	for path, s := range status {
+		if s.Worktree == git.Deleted || strings.HasPrefix(path, ".") {
+			continue
+		}
+		if s.Worktree != git.Unmodified || s.Staging != git.Unmodified {
+			edited = append(edited, path)
+		}
+	}
+	sort.Strings(edited)
+	return edited, nil
+}
@@ -0,0 +1,82 @@
+package helpers
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"reflect"
+	"time"
+
+	"github.com/fatih/color"
+)
+
+// WatchSecrets polls the secrets folder every interval and commits edited secrets once they stop changing,
+// it runs until the process is interrupted
+func WatchSecrets(secretsPath string, interval time.Duration, profile string, validators Validators) error {
+	if !CheckIfGitRepo(secretsPath, false) {
+		return fmt.Errorf("%s has no history yet, run jaws get first", secretsPath)
+	}
+	fmt.Printf("watching %s for changes every %s\n", secretsPath, interval)
+
+	var last, rejected map[string]time.Time
+	ticker := time.NewTicker(interval)
+	defer ticker.Stop()
+	for range ticker.C {
+		// the folder or its history is gone, nothing left to watch
+		if !CheckIfGitRepo(secretsPath, false) {
+			return fmt.Errorf("%s is no longer a git repository, stopped watching", secretsPath)
+		}
+		// anything else, like a held index lock, is logged and retried on the next poll
+		edited, err := EditedSecrets(secretsPath)
+		if err != nil {
+			watchWarning(err)
+			continue
+		}
+		if len(edited) == 0 {
+			last = nil
+			continue
+		}
+		current := map[string]time.Time{}
+		for _, id := range edited {
+			info, err := os.Stat(filepath.Join(secretsPath, id))
+			if err != nil {
+				// renamed or removed since the status was read, the next poll sees the new state
+				if !os.IsNotExist(err) {
+					watchWarning(err)
+				}
+				continue
+			}
+			current[id] = info.ModTime()
+		}
+		// wait for a poll with no new writes so half saved files are not committed
+		if !reflect.DeepEqual(current, last) {
+			last = current
+			continue
+		}
+		if reflect.DeepEqual(current, rejected) {
+			continue
+		}
+		if err = validators.ValidateAll(secretsPath, edited); err != nil {
+			color.Red(Redact(err.Error()))
+			rejected = current
+			continue
+		}
+		if err = GitControlSecrets(Snapshot{
+			Command:   "watch",
+			Profile:   profile,
+			SecretIDs: edited,
+		}, secretsPath); err != nil {
+			watchWarning(err)
+			last = nil
+			continue
+		}
+		fmt.Printf("%d secret(s) %s\n", len(edited), color.GreenString("committed"))
+		last = nil
+	}
+	return nil
+}
+
+// watchWarning reports an error that does not stop the watch
+func watchWarning(err error) {
+	fmt.Fprintln(os.Stderr, color.YellowString("watch: %s, retrying on the next poll", Redact(err.Error())))
+}